@@ -0,0 +1,35 @@
+#![no_main]
+
+use harsh::{DecodeError, Dialect, HarshError};
+use harsh_fuzz::Config;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Config, Vec<u64>)| {
+    let (config, values) = input;
+    let builder = config.builder();
+    let _ = builder.preview();
+    let _ = builder.max_exact_value();
+
+    let harsh = match builder.build() {
+        Ok(harsh) => harsh,
+        Err(_) => return,
+    };
+
+    let encoded = match harsh.try_encode(&values) {
+        Ok(encoded) if !values.is_empty() => encoded,
+        _ => return,
+    };
+
+    // The legacy dialect rounds values above 2^53, so they do not round-trip.
+    if harsh.dialect() == Dialect::Legacy && values.iter().any(|&value| value > 1 << 53) {
+        return;
+    }
+
+    match harsh.decode(&encoded) {
+        Ok(decoded) => assert_eq!(values, decoded),
+        Err(HarshError::Decode(
+            DecodeError::InputLength | DecodeError::ValueCount | DecodeError::ValueMagnitude,
+        )) => {}
+        Err(e) => panic!("{} failed to decode: {}", encoded, e),
+    }
+});
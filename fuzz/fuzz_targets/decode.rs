@@ -0,0 +1,22 @@
+#![no_main]
+
+use harsh_fuzz::Config;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Config, String)| {
+    let (config, encoded) = input;
+    if let Some(harsh) = config.build() {
+        let result = harsh.decode(&encoded);
+        assert_eq!(
+            result.as_ref().map_err(|e| e.to_string()),
+            harsh
+                .decode_detailed(&encoded)
+                .result
+                .as_ref()
+                .map_err(|stage| stage.error().to_string())
+        );
+        if let Ok(values) = result {
+            let _ = harsh.encode(&values);
+        }
+    }
+});
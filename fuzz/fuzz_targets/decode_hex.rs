@@ -0,0 +1,13 @@
+#![no_main]
+
+use harsh_fuzz::Config;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Config, String)| {
+    let (config, encoded) = input;
+    if let Some(harsh) = config.build() {
+        if let Ok(hex) = harsh.decode_hex(&encoded) {
+            let _ = harsh.encode_hex(&hex);
+        }
+    }
+});
@@ -0,0 +1,10 @@
+//! Shared input for the fuzz targets, which are run with `cargo fuzz run
+//! decode`, `cargo fuzz run decode_hex` or `cargo fuzz run build_config`.
+//!
+//! The configuration is the one the property tests use, in
+//! `tests/common/config.rs`.
+
+#[path = "../../tests/common/config.rs"]
+mod config;
+
+pub use config::{printable, Config};
@@ -0,0 +1,97 @@
+//! Derive macro for `harsh::HashIdValue`.
+//!
+//! This crate is re-exported by `harsh` with the `derive` feature enabled,
+//! and should not be used directly.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Index};
+
+/// Derives `HashIdValue` for a struct whose fields are all integers.
+///
+/// Fields are encoded in declaration order, each range-checked on
+/// conversion to and from `u64`.
+#[proc_macro_derive(HashIdValue)]
+pub fn derive_hash_id_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "HashIdValue can only be derived for structs",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "HashIdValue cannot be derived for a struct without fields",
+        ));
+    }
+
+    // Each field is copied out and converted to and from `u64`, which for
+    // fields of a generic type must be spelled out as bounds on the impl.
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = &field.ty;
+        where_clause.predicates.push(parse_quote!(
+            #ty: ::std::marker::Copy + ::std::convert::TryFrom<u64>
+        ));
+        where_clause
+            .predicates
+            .push(parse_quote!(u64: ::std::convert::TryFrom<#ty>));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let arity = fields.len();
+
+    let members: Vec<TokenStream2> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(index);
+                quote!(#index)
+            }
+        })
+        .collect();
+    let indices = 0..arity;
+
+    let to_values = quote! {
+        ::std::vec![#(::harsh::__private::to_value(self.#members, #indices)?),*]
+    };
+
+    let from_value =
+        (0..arity).map(|index| quote!(::harsh::__private::from_value(values, #index)?));
+    let construct = match fields {
+        Fields::Named(_) => quote!(#name { #(#members: #from_value),* }),
+        _ => quote!(#name(#(#from_value),*)),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::harsh::HashIdValue for #name #ty_generics #where_clause {
+            fn to_values(&self) -> ::std::result::Result<::std::vec::Vec<u64>, ::harsh::EncodeError> {
+                ::std::result::Result::Ok(#to_values)
+            }
+
+            fn from_values(values: &[u64]) -> ::std::result::Result<Self, ::harsh::DecodeError> {
+                ::harsh::__private::arity(values, #arity)?;
+                ::std::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
@@ -0,0 +1,39 @@
+use harsh::{DecodeError, Harsh, HarshError, HashIdValue};
+
+#[derive(Clone, Copy, Debug, PartialEq, HashIdValue)]
+struct Key<T> {
+    shard: u16,
+    local: T,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, HashIdValue)]
+struct Pair<A, B>(A, B)
+where
+    A: Copy;
+
+#[test]
+fn generic_fields_round_trip() {
+    let harsh = Harsh::default();
+
+    let key = Key {
+        shard: 3,
+        local: 42u32,
+    };
+    let id = harsh.encode_as(&key).unwrap();
+    assert_eq!(Ok(key), harsh.decode_as(&id));
+
+    let pair = Pair(1u8, 2u64);
+    let id = harsh.encode_as(&pair).unwrap();
+    assert_eq!(Ok(pair), harsh.decode_as(&id));
+}
+
+#[test]
+fn generic_fields_are_validated() {
+    let harsh = Harsh::default();
+    let id = harsh.encode(&[3, u64::from(u8::MAX) + 1]);
+
+    assert_eq!(
+        Err(HarshError::Decode(DecodeError::Range(1))),
+        harsh.decode_as::<Key<u8>, _>(&id)
+    );
+}
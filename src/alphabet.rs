@@ -0,0 +1,62 @@
+/// Predefined alphabets for common use cases.
+///
+/// Each preset comes with a matching set of separators, chosen so that
+/// separators need not be borrowed from the alphabet during initialization.
+/// Use [`HarshBuilder::preset`](./struct.HarshBuilder.html#method.preset) to
+/// configure both at once; passing a preset to `HarshBuilder::alphabet`
+/// configures the alphabet alone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Alphabet {
+    /// Letters and digits, excluding the lookalikes `0`, `O`, `o`, `1`, `l`
+    /// and `I`.
+    NoLookalikes,
+
+    /// Lowercase letters and digits, safe for DNS labels and subdomains.
+    Lowercase,
+
+    /// Uppercase letters and digits, encodable in QR alphanumeric mode.
+    Uppercase,
+
+    /// Crockford's base32 alphabet.
+    Crockford,
+
+    /// The default alphabet extended with `-` and `_`, safe for URLs.
+    UrlSafe,
+
+    /// Digits and lowercase consonants, excluding `l`, which is easily
+    /// mistaken for `1`, and `y`, which often acts as a vowel. Without
+    /// vowels, ids cannot spell words.
+    Consonants,
+}
+
+impl Alphabet {
+    /// The characters of the alphabet.
+    pub fn characters(self) -> &'static str {
+        match self {
+            Alphabet::NoLookalikes => "abcdefghijkmnpqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789",
+            Alphabet::Lowercase => "abcdefghijklmnopqrstuvwxyz1234567890",
+            Alphabet::Uppercase => "ABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890",
+            Alphabet::Crockford => "0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+            Alphabet::UrlSafe => "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890-_",
+            Alphabet::Consonants => "bcdfghjkmnpqrstvwxz1234567890",
+        }
+    }
+
+    /// The separators matching the alphabet.
+    pub fn separators(self) -> &'static str {
+        match self {
+            Alphabet::NoLookalikes => "cfhistuCFHSTU",
+            Alphabet::Lowercase => "cfhistux",
+            Alphabet::Uppercase => "CFHISTUX",
+            Alphabet::Crockford => "CFHSTVXZ",
+            Alphabet::UrlSafe => "cfhistuCFHISTU-",
+            Alphabet::Consonants => "cfhstvx",
+        }
+    }
+}
+
+impl From<Alphabet> for Vec<u8> {
+    fn from(alphabet: Alphabet) -> Vec<u8> {
+        alphabet.characters().into()
+    }
+}
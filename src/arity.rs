@@ -0,0 +1,151 @@
+use crate::{
+    harsh::{DecodeError, EncodeError, Harsh, HarshError},
+    value::__private,
+};
+use std::convert::TryFrom;
+
+/// Types that map to and from a fixed sequence of hashid values.
+///
+/// This is implemented for tuples of up to eight integers, and can be
+/// derived for structs of integer fields with the `derive` feature. Fields
+/// are encoded in declaration order, so reordering the fields of a struct
+/// changes its ids.
+///
+/// ```rust
+/// # use harsh::Harsh;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let harsh = Harsh::default();
+/// let id = harsh.encode_as(&(7u32, 42u64))?;
+///
+/// assert_eq!((7u32, 42u64), harsh.decode_as(&id)?);
+/// # Ok(())
+/// # }
+/// ```
+pub trait HashIdValue: Sized {
+    /// Converts into values, failing with `EncodeError::Range` if a field
+    /// cannot be represented as a `u64`.
+    fn to_values(&self) -> Result<Vec<u64>, EncodeError>;
+
+    /// Converts from decoded values, failing with `DecodeError::Arity` if
+    /// there are too many or too few, or `DecodeError::Range` if one does
+    /// not fit its field.
+    fn from_values(values: &[u64]) -> Result<Self, DecodeError>;
+}
+
+macro_rules! tuple {
+    ($len:expr; $($name:ident $index:tt),+) => {
+        impl<$($name: Copy + TryFrom<u64>),+> HashIdValue for ($($name,)+)
+        where
+            $(u64: TryFrom<$name>),+
+        {
+            fn to_values(&self) -> Result<Vec<u64>, EncodeError> {
+                Ok(vec![$(__private::to_value(self.$index, $index)?),+])
+            }
+
+            fn from_values(values: &[u64]) -> Result<Self, DecodeError> {
+                __private::arity(values, $len)?;
+                Ok(($(__private::from_value::<$name>(values, $index)?,)+))
+            }
+        }
+    };
+}
+
+tuple!(1; A 0);
+tuple!(2; A 0, B 1);
+tuple!(3; A 0, B 1, C 2);
+tuple!(4; A 0, B 1, C 2, D 3);
+tuple!(5; A 0, B 1, C 2, D 3, E 4);
+tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl Harsh {
+    /// Encodes a single `u64` value into a hashid.
+    pub fn encode_one(&self, value: u64) -> String {
+        self.encode(&[value])
+    }
+
+    /// Decodes a hashid that must contain exactly one value.
+    pub fn decode_one<T: AsRef<str>>(&self, input: T) -> Result<u64, HarshError> {
+        self.decode_array(input).map(|[value]| value)
+    }
+
+    /// Decodes a hashid that must contain exactly `N` values, failing with
+    /// `DecodeError::Arity` otherwise.
+    pub fn decode_array<const N: usize, T: AsRef<str>>(
+        &self,
+        input: T,
+    ) -> Result<[u64; N], HarshError> {
+        let values = self.decode(input)?;
+        <[u64; N]>::try_from(&values[..])
+            .map_err(|_| HarshError::Decode(DecodeError::Arity(values.len())))
+    }
+
+    /// Encodes a `HashIdValue`, such as a tuple of integers, into a hashid.
+    pub fn encode_as<V: HashIdValue>(&self, value: &V) -> Result<String, EncodeError> {
+        self.try_encode(&value.to_values()?)
+    }
+
+    /// Decodes a hashid into a `HashIdValue`, such as a `(u32, u64)` tuple.
+    ///
+    /// Fails with `DecodeError::Arity` if the hashid does not contain one
+    /// value per field, or `DecodeError::Range` if a value does not fit its
+    /// field.
+    pub fn decode_as<V: HashIdValue, T: AsRef<str>>(&self, input: T) -> Result<V, HarshError> {
+        let values = self.decode(input)?;
+        Ok(V::from_values(&values)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DecodeError, EncodeError, Harsh, HarshError};
+
+    #[test]
+    fn decode_one() {
+        let harsh = Harsh::default();
+        assert_eq!(Ok(42), harsh.decode_one(harsh.encode_one(42)));
+        assert_eq!(
+            Err(HarshError::Decode(DecodeError::Arity(2))),
+            harsh.decode_one(harsh.encode(&[1, 2]))
+        );
+    }
+
+    #[test]
+    fn decode_array() {
+        let harsh = Harsh::default();
+        assert_eq!(Ok([1, 2, 3]), harsh.decode_array("o2fXhV"));
+        assert_eq!(
+            Err(HarshError::Decode(DecodeError::Arity(3))),
+            harsh.decode_array::<2, _>("o2fXhV")
+        );
+        assert!(harsh.decode_array::<3, _>("o2fXhv").is_err());
+    }
+
+    #[test]
+    fn tuples_round_trip() {
+        let harsh = Harsh::default();
+        let id = harsh.encode_as(&(7u8, 1u64 << 32)).unwrap();
+
+        assert_eq!(harsh.encode(&[7, 1 << 32]), id);
+        assert_eq!(Ok((7u8, 1u64 << 32)), harsh.decode_as(&id));
+        assert_eq!(Ok((7i64, 1i64 << 32)), harsh.decode_as(&id));
+    }
+
+    #[test]
+    fn tuples_check_ranges() {
+        let harsh = Harsh::default();
+        let id = harsh.encode(&[7, u64::from(u32::MAX) + 1]);
+
+        assert_eq!(
+            Err(HarshError::Decode(DecodeError::Range(1))),
+            harsh.decode_as::<(u64, u32), _>(&id)
+        );
+        assert_eq!(
+            Err(HarshError::Decode(DecodeError::Arity(2))),
+            harsh.decode_as::<(u64, u64, u64), _>(&id)
+        );
+        assert_eq!(Err(EncodeError::Range(1)), harsh.encode_as(&(1i32, -1i32)));
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{harsh::Harsh, shuffle};
+use crate::{
+    alphabet::Alphabet,
+    dialect::Dialect,
+    harsh::{self, Harsh, Limits},
+    shuffle,
+};
 use std::{error, fmt, result};
 
 const DEFAULT_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
@@ -8,6 +13,7 @@ pub type Result<T, E = BuildHarshError> = result::Result<T, E>;
 
 /// Represents potential errors encountered during `Harsh` initialization.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum BuildHarshError {
     /// Error returned when the provided alphabet has insufficient distinct elements
     AlphabetLength,
@@ -17,6 +23,49 @@ pub enum BuildHarshError {
 
     /// Error returned when a separator character is not found in the alphabet
     Separator,
+
+    /// Error returned when the configuration version has no tag character in the alphabet
+    Version(u8),
+
+    /// Error returned when a versioned set includes a configuration without a version
+    Unversioned,
+
+    /// Error returned when a versioned set includes the same version or tag twice
+    DuplicateVersion(u8),
+
+    /// Error returned when the provided prefix is empty or not alphanumeric
+    Prefix,
+
+    /// Error returned when the alphabet contains two characters of the same confusable class
+    Confusable(char, char),
+
+    /// Error returned when an environment variable cannot be read or parsed
+    Environment(String),
+
+    /// Error returned when fewer than two characters remain in the alphabet
+    /// once separators and guards have been removed
+    AlphabetExhausted,
+
+    /// Error returned when a secret key is empty
+    Key,
+
+    /// Error returned when a permutation width is not between 2 and 64 bits
+    Width(u32),
+
+    /// Error returned when the limits or exact length of a configuration
+    /// reject some of the values a permutation produces
+    WidthLimit,
+
+    /// Error returned when a signature tag length is not between 1 and 8 bytes
+    TagLength(usize),
+
+    /// Error returned when the limits or exact length of a configuration
+    /// leave no room for a signature tag
+    TagLimit,
+
+    /// Error returned when a keyed wrapper would produce values wider than
+    /// the configuration's dialect encodes exactly
+    Dialect,
 }
 
 impl fmt::Display for BuildHarshError {
@@ -27,6 +76,28 @@ impl fmt::Display for BuildHarshError {
             "The provided alphabet contains an illegal character";
         static SEPARATOR_MESSAGE: &str =
             "The provided separators contain a character not found in the alphabet";
+        static VERSION_MESSAGE: &str =
+            "The provided alphabet is too short to tag the configuration version";
+        static UNVERSIONED_MESSAGE: &str =
+            "A versioned set requires every configuration to have a version";
+        static DUPLICATE_VERSION_MESSAGE: &str =
+            "A versioned set contains two configurations with the same version or tag";
+        static CONFUSABLE_MESSAGE: &str =
+            "The provided alphabet contains two characters declared confusable";
+        static ENVIRONMENT_MESSAGE: &str = "The environment variable could not be parsed";
+        static PREFIX_MESSAGE: &str =
+            "The provided prefix must be a non-empty ASCII alphanumeric string";
+        static ALPHABET_EXHAUSTED_MESSAGE: &str =
+            "The provided separators leave too few characters in the alphabet";
+        static KEY_MESSAGE: &str = "The provided secret key is empty";
+        static WIDTH_MESSAGE: &str = "The permutation width must be between 2 and 64 bits";
+        static WIDTH_LIMIT_MESSAGE: &str =
+            "The configured limits or exact length reject values of the permutation width";
+        static TAG_LENGTH_MESSAGE: &str = "The signature tag length must be between 1 and 8 bytes";
+        static TAG_LIMIT_MESSAGE: &str =
+            "The configured limits or exact length leave no room for the signature tag";
+        static DIALECT_MESSAGE: &str =
+            "The configured dialect cannot encode values of this width exactly";
 
         match self {
             BuildHarshError::AlphabetLength => write!(f, "{}", ALPHABET_LENGTH_MESSAGE),
@@ -34,6 +105,23 @@ impl fmt::Display for BuildHarshError {
                 write!(f, "{} ({})", ILLEGAL_CHARACTER_MESSAGE, c)
             }
             BuildHarshError::Separator => write!(f, "{}", SEPARATOR_MESSAGE),
+            BuildHarshError::Version(version) => write!(f, "{} ({})", VERSION_MESSAGE, version),
+            BuildHarshError::Unversioned => write!(f, "{}", UNVERSIONED_MESSAGE),
+            BuildHarshError::DuplicateVersion(version) => {
+                write!(f, "{} ({})", DUPLICATE_VERSION_MESSAGE, version)
+            }
+            BuildHarshError::Prefix => write!(f, "{}", PREFIX_MESSAGE),
+            BuildHarshError::Confusable(a, b) => {
+                write!(f, "{} ({}, {})", CONFUSABLE_MESSAGE, a, b)
+            }
+            BuildHarshError::Environment(key) => write!(f, "{} ({})", ENVIRONMENT_MESSAGE, key),
+            BuildHarshError::AlphabetExhausted => write!(f, "{}", ALPHABET_EXHAUSTED_MESSAGE),
+            BuildHarshError::Key => write!(f, "{}", KEY_MESSAGE),
+            BuildHarshError::Width(bits) => write!(f, "{} ({})", WIDTH_MESSAGE, bits),
+            BuildHarshError::WidthLimit => write!(f, "{}", WIDTH_LIMIT_MESSAGE),
+            BuildHarshError::TagLength(len) => write!(f, "{} ({})", TAG_LENGTH_MESSAGE, len),
+            BuildHarshError::TagLimit => write!(f, "{}", TAG_LIMIT_MESSAGE),
+            BuildHarshError::Dialect => write!(f, "{}", DIALECT_MESSAGE),
         }
     }
 }
@@ -41,12 +129,20 @@ impl fmt::Display for BuildHarshError {
 impl error::Error for BuildHarshError {}
 
 /// A builder used to configure and create a Harsh instance.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct HarshBuilder {
     salt: Option<Vec<u8>>,
     alphabet: Option<Vec<u8>>,
     separators: Option<Vec<u8>>,
     hash_length: usize,
+    exact_length: bool,
+    version: Option<u8>,
+    prefix: Option<String>,
+    prefix_salt: bool,
+    check_character: bool,
+    confusables: Vec<Vec<u8>>,
+    limits: Limits,
+    dialect: Dialect,
 }
 
 impl HarshBuilder {
@@ -57,6 +153,14 @@ impl HarshBuilder {
             alphabet: None,
             separators: None,
             hash_length: 0,
+            exact_length: false,
+            version: None,
+            prefix: None,
+            prefix_salt: false,
+            check_character: false,
+            confusables: Vec::new(),
+            limits: Limits::default(),
+            dialect: Dialect::default(),
         }
     }
 
@@ -71,8 +175,11 @@ impl HarshBuilder {
 
     /// Provides an alphabet.
     ///
-    /// Note that this alphabet will be converted into a `[u8]` before use, meaning
-    /// that multi-byte utf8 character values should be avoided.
+    /// Note that this alphabet will be converted into a `[u8]` before use, so
+    /// only ASCII characters are accepted; others fail the build with
+    /// `BuildHarshError::IllegalCharacter`. Separators that leave fewer than
+    /// two characters in the alphabet fail with
+    /// `BuildHarshError::AlphabetExhausted`.
     pub fn alphabet<T: Into<Vec<u8>>>(mut self, alphabet: T) -> HarshBuilder {
         self.alphabet = Some(alphabet.into());
         self
@@ -87,40 +194,272 @@ impl HarshBuilder {
         self
     }
 
+    /// Provides a predefined alphabet along with its matching separators.
+    pub fn preset(self, preset: Alphabet) -> HarshBuilder {
+        self.alphabet(preset).separators(preset.separators())
+    }
+
+    /// Selects the reference implementation to reproduce, `HashidsJs` by
+    /// default.
+    ///
+    /// All dialects derive the same alphabet, separators and guards; see
+    /// `Dialect` for where their ids differ.
+    pub fn dialect(mut self, dialect: Dialect) -> HarshBuilder {
+        self.dialect = dialect;
+        self
+    }
+
     /// Provides a minimum hash length.
     ///
     /// Keep in mind that hashes produced may be longer than this length.
     pub fn length(mut self, hash_length: usize) -> HarshBuilder {
         self.hash_length = hash_length;
+        self.exact_length = false;
+        self
+    }
+
+    /// Provides an exact hash length.
+    ///
+    /// Hashes shorter than this length are padded as usual, but values that
+    /// would produce a longer hash cannot be encoded: `Harsh::try_encode` will
+    /// fail and `Harsh::encode` will return an empty string. Version tags,
+    /// check characters and prefixes are added on top of this length.
+    pub fn exact_length(mut self, hash_length: usize) -> HarshBuilder {
+        self.hash_length = hash_length;
+        self.exact_length = true;
+        self
+    }
+
+    /// Returns the largest single value whose hash fits within the configured
+    /// length, or `None` if the length is too short to hold any value.
+    ///
+    /// When an exact length is configured, this is the largest single value
+    /// that can be encoded.
+    pub fn max_exact_value(&self) -> Result<Option<u64>> {
+        let derived = self.derive()?;
+        Ok(harsh::max_value(
+            self.dialect,
+            derived.alphabet.len(),
+            self.hash_length,
+        ))
+    }
+
+    /// Provides a configuration version.
+    ///
+    /// When set, every hashid is prefixed with a tag character identifying
+    /// this version, taken from the (unshuffled) alphabet at the version's
+    /// index. This allows a `VersionedHarsh` to route an id to the
+    /// configuration that produced it. The tag does not count toward the
+    /// minimum hash length.
+    pub fn version(mut self, version: u8) -> HarshBuilder {
+        self.version = Some(version);
+        self
+    }
+
+    /// Provides a prefix, rendering hashids as `<prefix>_<hashid>`.
+    ///
+    /// Decoding requires and strips the prefix, failing with
+    /// `DecodeError::Prefix` when it is missing or different. The prefix must
+    /// be ASCII alphanumeric and does not count toward the minimum hash length.
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> HarshBuilder {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Derives the salt from both the provided salt and the prefix.
+    ///
+    /// This keeps ids with different prefixes from sharing a hashid for the
+    /// same values, so that swapping one prefix for another does not yield a
+    /// valid id.
+    pub fn prefix_salt(mut self, prefix_salt: bool) -> HarshBuilder {
+        self.prefix_salt = prefix_salt;
+        self
+    }
+
+    /// Appends a check character to every hashid.
+    ///
+    /// The check character is computed over the whole id (excluding any
+    /// prefix) and is verified on decode, catching every single-character
+    /// substitution and every transposition of adjacent characters with
+    /// `DecodeError::Check`. The check requires an odd number of characters,
+    /// so the last character of an even-sized alphabet goes unused.
+    pub fn check_character(mut self, check_character: bool) -> HarshBuilder {
+        self.check_character = check_character;
+        self
+    }
+
+    /// Provides classes of characters that are easily confused for one another,
+    /// such as `["0Oo", "1lI", "5S"]`.
+    ///
+    /// The alphabet may contain at most one member of each class, and decoding
+    /// replaces the other members with that character before parsing. Classes
+    /// must consist of ASCII characters.
+    pub fn confusables<I, T>(mut self, confusables: I) -> HarshBuilder
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Vec<u8>>,
+    {
+        self.confusables = confusables.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Provides a maximum length for hashids accepted by `decode`.
+    ///
+    /// Longer input is rejected with `DecodeError::InputLength` before any
+    /// other work is done.
+    pub fn max_input_length(mut self, max: usize) -> HarshBuilder {
+        self.limits.input_length = Some(max);
         self
     }
 
+    /// Provides a maximum number of values accepted by `decode`.
+    ///
+    /// Hashids containing more values are rejected with
+    /// `DecodeError::ValueCount` before any value is parsed.
+    pub fn max_values(mut self, max: usize) -> HarshBuilder {
+        self.limits.values = Some(max);
+        self
+    }
+
+    /// Provides a maximum value accepted by `decode`.
+    ///
+    /// Hashids containing a larger value are rejected with
+    /// `DecodeError::ValueMagnitude`; segments too long to hold a value within
+    /// the limit are rejected before being parsed.
+    pub fn max_value(mut self, max: u64) -> HarshBuilder {
+        self.limits.value = Some(max);
+        self
+    }
+
+    /// Derives the alphabet, separators and guards a `Harsh` built from this
+    /// configuration would use, without building it.
+    pub fn preview(&self) -> Result<HarshPreview> {
+        let derived = self.derive()?;
+        Ok(HarshPreview {
+            alphabet: derived.alphabet,
+            separators: derived.separators,
+            guards: derived.guards,
+        })
+    }
+
     /// Initializes a new `Harsh` based on the `HarshBuilder`.
     ///
     /// This method will consume the `HarshBuilder`.
     pub fn build(self) -> Result<Harsh> {
+        let derived = self.derive()?;
+
+        Ok(Harsh {
+            alphabet: derived.alphabet.into_boxed_slice(),
+            guards: derived.guards.into_boxed_slice(),
+            hash_length: self.hash_length,
+            exact_length: self.exact_length,
+            salt: derived.salt.into_boxed_slice(),
+            separators: derived.separators.into_boxed_slice(),
+            version: self.version.zip(derived.tag),
+            prefix: self.prefix.map(String::into_boxed_str),
+            check: derived.check.map(Vec::into_boxed_slice),
+            normalization: derived.normalization.into_boxed_slice(),
+            limits: self.limits,
+            dialect: self.dialect,
+        })
+    }
+
+    /// Validates the configuration and derives the character sets used by
+    /// `Harsh`.
+    fn derive(&self) -> Result<Derived> {
         const MINIMUM_ALPHABET_LENGTH: usize = 16;
 
-        let alphabet = unique_alphabet(&self.alphabet)?;
+        let mut alphabet = unique_alphabet(&self.alphabet)?;
         if alphabet.len() < MINIMUM_ALPHABET_LENGTH {
             return Err(BuildHarshError::AlphabetLength);
         }
 
-        let salt = self.salt.unwrap_or_else(Vec::new);
+        if self.check_character && alphabet.len() % 2 == 0 {
+            alphabet.pop();
+        }
+        let check = if self.check_character {
+            Some(alphabet.clone())
+        } else {
+            None
+        };
+
+        let normalization = normalization(&self.confusables, &alphabet)?;
+
+        let tag = match self.version {
+            None => None,
+            Some(version) => match alphabet.get(version as usize) {
+                Some(&tag) => Some(tag),
+                None => return Err(BuildHarshError::Version(version)),
+            },
+        };
+
+        if let Some(prefix) = &self.prefix {
+            if prefix.is_empty() || !prefix.bytes().all(|u| u.is_ascii_alphanumeric()) {
+                return Err(BuildHarshError::Prefix);
+            }
+        }
+
+        let mut salt = self.salt.clone().unwrap_or_default();
+        if let (Some(prefix), true) = (&self.prefix, self.prefix_salt) {
+            salt.push(b'_');
+            salt.extend_from_slice(prefix.as_bytes());
+        }
+
         let (mut alphabet, mut separators) =
             alphabet_and_separators(&self.separators, &alphabet, &salt);
         let guards = guards(&mut alphabet, &mut separators);
+        if alphabet.len() < 2 {
+            return Err(BuildHarshError::AlphabetExhausted);
+        }
 
-        Ok(Harsh::initialize(
-            alphabet.into_boxed_slice(),
-            guards.into_boxed_slice(),
-            self.hash_length,
-            salt.into_boxed_slice(),
-            separators.into_boxed_slice(),
-        ))
+        Ok(Derived {
+            alphabet,
+            separators,
+            guards,
+            salt,
+            check,
+            normalization,
+            tag,
+        })
+    }
+}
+
+/// The character sets a `Harsh` would derive from a `HarshBuilder`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HarshPreview {
+    alphabet: Vec<u8>,
+    separators: Vec<u8>,
+    guards: Vec<u8>,
+}
+
+impl HarshPreview {
+    /// See `Harsh::alphabet`.
+    pub fn alphabet(&self) -> &[u8] {
+        &self.alphabet
+    }
+
+    /// See `Harsh::separators`.
+    pub fn separators(&self) -> &[u8] {
+        &self.separators
+    }
+
+    /// See `Harsh::guards`.
+    pub fn guards(&self) -> &[u8] {
+        &self.guards
     }
 }
 
+/// The character sets derived from a `HarshBuilder` configuration.
+struct Derived {
+    alphabet: Vec<u8>,
+    separators: Vec<u8>,
+    guards: Vec<u8>,
+    salt: Vec<u8>,
+    check: Option<Vec<u8>>,
+    normalization: Vec<(u8, u8)>,
+    tag: Option<u8>,
+}
+
 fn unique_alphabet(alphabet: &Option<Vec<u8>>) -> Result<Vec<u8>> {
     use std::collections::HashSet;
 
@@ -136,10 +475,19 @@ fn unique_alphabet(alphabet: &Option<Vec<u8>>) -> Result<Vec<u8>> {
             let mut ret = Vec::new();
 
             for &item in alphabet {
-                if item == b' ' {
+                // A leading NUL would be used as a zero divisor while encoding.
+                if item == b' ' || item == b'\0' {
                     return Err(BuildHarshError::IllegalCharacter(item as char));
                 }
 
+                if !item.is_ascii() {
+                    let c = String::from_utf8_lossy(alphabet)
+                        .chars()
+                        .find(|c| !c.is_ascii())
+                        .unwrap_or(char::REPLACEMENT_CHARACTER);
+                    return Err(BuildHarshError::IllegalCharacter(c));
+                }
+
                 if !reg.contains(&item) {
                     ret.push(item);
                     reg.insert(item);
@@ -155,6 +503,38 @@ fn unique_alphabet(alphabet: &Option<Vec<u8>>) -> Result<Vec<u8>> {
     }
 }
 
+fn normalization(confusables: &[Vec<u8>], alphabet: &[u8]) -> Result<Vec<(u8, u8)>> {
+    let mut normalization = Vec::new();
+
+    for class in confusables {
+        if let Some(&u) = class.iter().find(|u| !u.is_ascii()) {
+            return Err(BuildHarshError::IllegalCharacter(u as char));
+        }
+
+        let mut members = class.iter().filter(|u| alphabet.contains(u));
+        let canonical = match members.next() {
+            Some(&canonical) => canonical,
+            None => continue,
+        };
+
+        if let Some(&other) = members.find(|&&u| u != canonical) {
+            return Err(BuildHarshError::Confusable(
+                canonical as char,
+                other as char,
+            ));
+        }
+
+        normalization.extend(
+            class
+                .iter()
+                .filter(|&&u| u != canonical)
+                .map(|&u| (u, canonical)),
+        );
+    }
+
+    Ok(normalization)
+}
+
 fn alphabet_and_separators(
     separators: &Option<Vec<u8>>,
     alphabet: &[u8],
@@ -205,7 +585,8 @@ fn guards(alphabet: &mut Vec<u8>, separators: &mut Vec<u8>) -> Vec<u8> {
     let guard_count = (alphabet.len() as f64 / GUARD_DIV).ceil() as usize;
     if alphabet.len() < 3 {
         let guards = separators[..guard_count].to_vec();
-        separators.drain(..guard_count);
+        // Separators are not deduplicated, so drop every copy of the guards.
+        separators.retain(|u| !guards.contains(u));
         guards
     } else {
         let guards = alphabet[..guard_count].to_vec();
@@ -237,6 +618,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn presets_keep_their_separators() {
+        use crate::Alphabet;
+
+        let presets = [
+            Alphabet::NoLookalikes,
+            Alphabet::Lowercase,
+            Alphabet::Uppercase,
+            Alphabet::Crockford,
+            Alphabet::UrlSafe,
+            Alphabet::Consonants,
+        ];
+
+        for &preset in &presets {
+            let (alphabet, mut separators) = super::alphabet_and_separators(
+                &Some(preset.separators().into()),
+                preset.characters().as_bytes(),
+                b"this is my salt",
+            );
+
+            separators.sort_unstable();
+            let mut expected = preset.separators().as_bytes().to_vec();
+            expected.sort_unstable();
+
+            assert_eq!(expected, separators, "{:?}", preset);
+            assert_eq!(
+                preset.characters().len(),
+                alphabet.len() + separators.len(),
+                "{:?}",
+                preset
+            );
+        }
+    }
+
     #[test]
     fn alphabet_and_separator_generation_with_few_separators() {
         use super::DEFAULT_ALPHABET;
@@ -0,0 +1,231 @@
+use crate::{dialect::Dialect, harsh::Harsh};
+use std::collections::BTreeMap;
+
+/// The number of distinct single-value hashids of a given length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capacity {
+    /// The length of the hashids, including any prefix, version tag and check
+    /// character.
+    pub length: usize,
+    /// The number of values encoded into hashids of this length.
+    pub count: u128,
+}
+
+impl Harsh {
+    /// Returns the length of the hashid `encode` would produce for `values`,
+    /// without producing it.
+    ///
+    /// Like `encode`, this is zero for values that cannot be encoded.
+    pub fn encoded_len(&self, values: &[u64]) -> usize {
+        if values.is_empty() {
+            return 0;
+        }
+
+        let base = self.alphabet.len();
+        let digits: usize = values
+            .iter()
+            .map(|&value| self.dialect.digits(value, base))
+            .sum();
+        self.decorated_len(1 + digits + values.len() - 1)
+    }
+
+    /// Returns the length of the longest hashid `encode` can produce for
+    /// `arity` values.
+    pub fn max_encoded_len(&self, arity: usize) -> usize {
+        if arity == 0 {
+            return 0;
+        }
+
+        let digits = self.dialect.digits(u64::MAX, self.alphabet.len());
+        let hash_length = arity.saturating_mul(digits + 1);
+        if self.exact_length {
+            self.decorated_len(hash_length.min(self.hash_length))
+        } else {
+            self.decorated_len(hash_length)
+        }
+    }
+
+    /// Reports how many distinct single-value hashids exist at each length.
+    ///
+    /// Values are hashed with the alphabet left after separators and guards
+    /// have been removed; every value whose hash is shorter than the minimum
+    /// length is counted at that length. Lengths are in ascending order and
+    /// together cover every value `encode` accepts.
+    pub fn capacity(&self) -> Vec<Capacity> {
+        let base = self.alphabet.len();
+        let mut lengths = BTreeMap::new();
+
+        let (mut low, mut digits) = (0u128, 1);
+        while low <= u64::MAX as u128 {
+            let high = digit_limit(self.dialect, digits, base);
+            let length = self.decorated_len(1 + digits);
+            if length > 0 {
+                *lengths.entry(length).or_insert(0) += high - low;
+            }
+
+            low = high;
+            digits += 1;
+        }
+
+        lengths
+            .into_iter()
+            .map(|(length, count)| Capacity { length, count })
+            .collect()
+    }
+
+    /// The length of the final hashid for an unpadded hash of `hash_length`,
+    /// or zero if it exceeds an exact hash length.
+    fn decorated_len(&self, hash_length: usize) -> usize {
+        if self.exact_length && hash_length > self.hash_length {
+            return 0;
+        }
+
+        let mut length = hash_length.max(self.hash_length);
+        if self.version.is_some() {
+            length = length.saturating_add(1);
+        }
+        if self.check.is_some() {
+            length = length.saturating_add(1);
+        }
+        if let Some(prefix) = &self.prefix {
+            length = length.saturating_add(prefix.len() + 1);
+        }
+        length
+    }
+}
+
+/// The number of digits in `value` written in base `base`.
+pub(crate) fn digits(mut value: u64, base: usize) -> usize {
+    let mut digits = 1;
+    while value >= base as u64 {
+        value /= base as u64;
+        digits += 1;
+    }
+    digits
+}
+
+/// The smallest value that `dialect` hashes to more than `digits`
+/// characters with an alphabet of `base` characters, or `2^64` if there is
+/// none.
+pub(crate) fn digit_limit(dialect: Dialect, digits: usize, base: usize) -> u128 {
+    // The number of digits never decreases as values grow, so the limit can
+    // be found by bisection, whatever rounding the dialect applies.
+    let (mut low, mut high) = (0u128, u64::MAX as u128 + 1);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if dialect.digits(mid as u64, base) > digits {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Capacity;
+    use crate::{Dialect, Harsh};
+
+    const VALUES: &[&[u64]] = &[
+        &[0],
+        &[1, 2, 3],
+        &[43, 44, 45],
+        &[1000000000000],
+        &[u64::MAX],
+        &[u64::MAX, 0, u64::MAX],
+        &[44u64.pow(10) - 1],
+    ];
+
+    #[test]
+    fn encoded_len_matches_encode() {
+        let builders = vec![
+            Harsh::builder(),
+            Harsh::builder().salt("this is my salt").length(12),
+            Harsh::builder().exact_length(6),
+            Harsh::builder()
+                .prefix("cus")
+                .version(2)
+                .check_character(true)
+                .length(4),
+            Harsh::builder().dialect(Dialect::Legacy),
+            Harsh::builder().dialect(Dialect::Legacy).exact_length(11),
+        ];
+
+        for builder in builders {
+            let harsh = builder.build().unwrap();
+            for &values in VALUES {
+                assert_eq!(harsh.encode(values).len(), harsh.encoded_len(values));
+            }
+        }
+    }
+
+    #[test]
+    fn max_encoded_len() {
+        let harsh = Harsh::default();
+        assert_eq!(0, harsh.max_encoded_len(0));
+        assert_eq!(harsh.encode(&[u64::MAX]).len(), harsh.max_encoded_len(1));
+        assert_eq!(
+            harsh.encode(&[u64::MAX, u64::MAX]).len(),
+            harsh.max_encoded_len(2)
+        );
+
+        let harsh = Harsh::builder().exact_length(6).build().unwrap();
+        assert_eq!(6, harsh.max_encoded_len(3));
+
+        let harsh = Harsh::builder().dialect(Dialect::Legacy).build().unwrap();
+        assert_eq!(harsh.encode(&[u64::MAX]).len(), harsh.max_encoded_len(1));
+    }
+
+    #[test]
+    fn capacity() {
+        // 44 characters remain in the default alphabet.
+        let harsh = Harsh::builder().length(3).build().unwrap();
+        let capacity = harsh.capacity();
+
+        assert_eq!(
+            Capacity {
+                length: 3,
+                count: 44 * 44
+            },
+            capacity[0]
+        );
+        assert_eq!(
+            Capacity {
+                length: 4,
+                count: 44 * 44 * 44 - 44 * 44
+            },
+            capacity[1]
+        );
+        assert_eq!(
+            u64::MAX as u128 + 1,
+            capacity.iter().map(|c| c.count).sum::<u128>()
+        );
+    }
+
+    #[test]
+    fn legacy_capacity() {
+        // Values just below 44^10 round up to it and take eleven digits.
+        let harsh = Harsh::builder().dialect(Dialect::Legacy).build().unwrap();
+        let capacity = harsh.capacity();
+        let ten_digits = capacity.iter().find(|c| c.length == 11).unwrap();
+
+        assert!(ten_digits.count < 44u128.pow(10) - 44u128.pow(9));
+        assert_eq!(
+            u64::MAX as u128 + 1,
+            capacity.iter().map(|c| c.count).sum::<u128>()
+        );
+    }
+
+    #[test]
+    fn exact_length_capacity() {
+        let harsh = Harsh::builder().exact_length(3).build().unwrap();
+        assert_eq!(
+            vec![Capacity {
+                length: 3,
+                count: 44 * 44
+            }],
+            harsh.capacity()
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    builder::{BuildHarshError, HarshBuilder, Result},
+    dialect::Dialect,
+    harsh::Harsh,
+};
+use std::{convert::TryFrom, env, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A plain description of a `Harsh` configuration.
+///
+/// Unlike `HarshBuilder`, this can be read from configuration files (with the
+/// `serde` feature enabled) or from the environment, and then converted into
+/// a `HarshBuilder` or directly into a `Harsh`. Every field is optional, and
+/// omitted fields take the same defaults as `HarshBuilder`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+#[non_exhaustive]
+pub struct HarshConfig {
+    /// See `HarshBuilder::salt`.
+    pub salt: Option<String>,
+    /// See `HarshBuilder::alphabet`.
+    pub alphabet: Option<String>,
+    /// See `HarshBuilder::separators`.
+    pub separators: Option<String>,
+    /// See `HarshBuilder::length`.
+    pub length: usize,
+    /// Treats `length` as an exact length; see `HarshBuilder::exact_length`.
+    pub exact_length: bool,
+    /// See `HarshBuilder::version`.
+    pub version: Option<u8>,
+    /// See `HarshBuilder::prefix`.
+    pub prefix: Option<String>,
+    /// See `HarshBuilder::prefix_salt`.
+    pub prefix_salt: bool,
+    /// See `HarshBuilder::check_character`.
+    pub check_character: bool,
+    /// See `HarshBuilder::confusables`.
+    pub confusables: Vec<String>,
+    /// See `HarshBuilder::max_input_length`.
+    pub max_input_length: Option<usize>,
+    /// See `HarshBuilder::max_values`.
+    pub max_values: Option<usize>,
+    /// See `HarshBuilder::max_value`.
+    pub max_value: Option<u64>,
+    /// See `HarshBuilder::dialect`; written `hashids_js` or `legacy`.
+    pub dialect: Dialect,
+}
+
+impl HarshConfig {
+    /// Reads a configuration from environment variables named after `prefix`.
+    ///
+    /// For a prefix of `IDS`, the variables read are `IDS_SALT`,
+    /// `IDS_ALPHABET`, `IDS_SEPARATORS`, `IDS_LENGTH`, `IDS_EXACT_LENGTH`,
+    /// `IDS_VERSION`, `IDS_PREFIX`, `IDS_PREFIX_SALT`, `IDS_CHECK_CHARACTER`,
+    /// `IDS_CONFUSABLES` (whitespace-separated classes),
+    /// `IDS_MAX_INPUT_LENGTH`, `IDS_MAX_VALUES`, `IDS_MAX_VALUE` and
+    /// `IDS_DIALECT` (`hashids_js` or `legacy`). Unset
+    /// variables keep their defaults; variables that are not valid unicode or
+    /// cannot be parsed are reported with `BuildHarshError::Environment`.
+    pub fn from_env(prefix: &str) -> Result<HarshConfig> {
+        Ok(HarshConfig {
+            salt: var(prefix, "SALT")?,
+            alphabet: var(prefix, "ALPHABET")?,
+            separators: var(prefix, "SEPARATORS")?,
+            length: parse(prefix, "LENGTH")?.unwrap_or_default(),
+            exact_length: parse(prefix, "EXACT_LENGTH")?.unwrap_or_default(),
+            version: parse(prefix, "VERSION")?,
+            prefix: var(prefix, "PREFIX")?,
+            prefix_salt: parse(prefix, "PREFIX_SALT")?.unwrap_or_default(),
+            check_character: parse(prefix, "CHECK_CHARACTER")?.unwrap_or_default(),
+            confusables: var(prefix, "CONFUSABLES")?
+                .map(|classes| classes.split_whitespace().map(String::from).collect())
+                .unwrap_or_default(),
+            max_input_length: parse(prefix, "MAX_INPUT_LENGTH")?,
+            max_values: parse(prefix, "MAX_VALUES")?,
+            max_value: parse(prefix, "MAX_VALUE")?,
+            dialect: dialect(prefix)?,
+        })
+    }
+}
+
+fn var(prefix: &str, name: &str) -> Result<Option<String>> {
+    let key = format!("{}_{}", prefix, name);
+    match env::var(&key) {
+        Ok(value) => Ok(Some(value)),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(env::VarError::NotUnicode(_)) => Err(BuildHarshError::Environment(key)),
+    }
+}
+
+fn parse<T: FromStr>(prefix: &str, name: &str) -> Result<Option<T>> {
+    match var(prefix, name)? {
+        None => Ok(None),
+        Some(value) => match value.trim().parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(BuildHarshError::Environment(format!("{}_{}", prefix, name))),
+        },
+    }
+}
+
+fn dialect(prefix: &str) -> Result<Dialect> {
+    match var(prefix, "DIALECT")?.as_deref().map(str::trim) {
+        None | Some("hashids_js") => Ok(Dialect::HashidsJs),
+        Some("legacy") => Ok(Dialect::Legacy),
+        Some(_) => Err(BuildHarshError::Environment(format!("{}_DIALECT", prefix))),
+    }
+}
+
+impl From<HarshConfig> for HarshBuilder {
+    fn from(config: HarshConfig) -> HarshBuilder {
+        let mut builder = HarshBuilder::new()
+            .prefix_salt(config.prefix_salt)
+            .check_character(config.check_character)
+            .confusables(config.confusables)
+            .dialect(config.dialect);
+
+        builder = if config.exact_length {
+            builder.exact_length(config.length)
+        } else {
+            builder.length(config.length)
+        };
+
+        if let Some(salt) = config.salt {
+            builder = builder.salt(salt);
+        }
+        if let Some(alphabet) = config.alphabet {
+            builder = builder.alphabet(alphabet);
+        }
+        if let Some(separators) = config.separators {
+            builder = builder.separators(separators);
+        }
+        if let Some(version) = config.version {
+            builder = builder.version(version);
+        }
+        if let Some(prefix) = config.prefix {
+            builder = builder.prefix(prefix);
+        }
+        if let Some(max) = config.max_input_length {
+            builder = builder.max_input_length(max);
+        }
+        if let Some(max) = config.max_values {
+            builder = builder.max_values(max);
+        }
+        if let Some(max) = config.max_value {
+            builder = builder.max_value(max);
+        }
+
+        builder
+    }
+}
+
+impl TryFrom<HarshConfig> for Harsh {
+    type Error = BuildHarshError;
+
+    fn try_from(config: HarshConfig) -> Result<Harsh> {
+        HarshBuilder::from(config).build()
+    }
+}
+
+impl HarshBuilder {
+    /// Creates a `HarshBuilder` from environment variables named after
+    /// `prefix`, as described by `HarshConfig::from_env`.
+    pub fn from_env(prefix: &str) -> Result<HarshBuilder> {
+        HarshConfig::from_env(prefix).map(HarshBuilder::from)
+    }
+}
@@ -0,0 +1,221 @@
+use crate::harsh::{DecodeError, Harsh};
+use std::{error, fmt};
+
+/// A description of how a hashid was parsed by `Harsh::decode_detailed`.
+///
+/// The regions are reported in the order they appear in the hashid, after
+/// confusable characters have been normalized and the prefix, check
+/// character and version tag have been stripped. Regions the parse did not
+/// reach are left empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DecodeReport {
+    /// The input, with confusable characters normalized unless it exceeded
+    /// the input length limit.
+    pub input: String,
+    /// Padding characters preceding the left guard.
+    pub left_padding: String,
+    /// The guard character stripped from the left, if any.
+    pub left_guard: Option<char>,
+    /// The lottery character that seeds the alphabet shuffles.
+    pub lottery: Option<char>,
+    /// The value segments, in order.
+    pub segments: Vec<Segment>,
+    /// The guard character stripped from the right, if any.
+    pub right_guard: Option<char>,
+    /// Padding characters following the right guard.
+    pub right_padding: String,
+    /// The decoded values, or the stage at which decoding failed.
+    pub result: Result<Vec<u64>, DecodeStage>,
+}
+
+/// A single value segment of a hashid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Segment {
+    /// The characters encoding the value.
+    pub text: String,
+    /// The separator following the segment, unless it is the last one.
+    pub separator: Option<char>,
+    /// The decoded value, if the segment could be unhashed.
+    pub value: Option<u64>,
+}
+
+/// The stage at which `Harsh::decode_detailed` failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeStage {
+    /// A configured decoder limit was exceeded.
+    Limit(DecodeError),
+    /// The prefix, check character or version tag did not match.
+    Decoration(DecodeError),
+    /// Too few characters remained once guards were stripped.
+    Guards,
+    /// The segment at this index contains characters outside the alphabet.
+    Unhash { segment: usize },
+    /// The segment at this index encodes a value that does not fit a `u64`.
+    Overflow { segment: usize },
+    /// The values decoded, but encode to this different hashid.
+    Canonical { expected: String },
+}
+
+impl DecodeStage {
+    /// The error `Harsh::decode` reports for a failure at this stage.
+    pub fn error(&self) -> DecodeError {
+        match self {
+            DecodeStage::Limit(e) | DecodeStage::Decoration(e) => e.clone(),
+            DecodeStage::Guards | DecodeStage::Canonical { .. } => DecodeError::Hash,
+            DecodeStage::Unhash { .. } => DecodeError::Value,
+            DecodeStage::Overflow { .. } => DecodeError::Overflow,
+        }
+    }
+}
+
+impl fmt::Display for DecodeStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeStage::Limit(e) | DecodeStage::Decoration(e) => e.fmt(f),
+            DecodeStage::Guards => f.write_str("Too few characters left after stripping guards"),
+            DecodeStage::Unhash { segment } => {
+                write!(
+                    f,
+                    "Segment {} contains characters outside the alphabet",
+                    segment
+                )
+            }
+            DecodeStage::Overflow { segment } => {
+                write!(f, "Segment {} encodes a value larger than u64", segment)
+            }
+            DecodeStage::Canonical { expected } => {
+                write!(f, "Values re-encode to a different hashid: {}", expected)
+            }
+        }
+    }
+}
+
+impl error::Error for DecodeStage {}
+
+impl Harsh {
+    /// Decodes a hashid, describing each step of the parse.
+    ///
+    /// This shares its implementation with `decode`, so it accepts and
+    /// rejects exactly the same input, but reports the guards, padding,
+    /// lottery character and segments it found along the way, and on failure
+    /// the stage that failed. It is intended for debugging ids that fail to
+    /// decode.
+    pub fn decode_detailed(&self, input: &str) -> DecodeReport {
+        let mut report = DecodeReport {
+            input: input.to_owned(),
+            left_padding: String::new(),
+            left_guard: None,
+            lottery: None,
+            segments: Vec::new(),
+            right_guard: None,
+            right_padding: String::new(),
+            result: Ok(Vec::new()),
+        };
+        report.result = self.decode_with(input, &mut Some(&mut report));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecodeStage;
+    use crate::{DecodeError, Harsh};
+
+    #[test]
+    fn reports_regions() {
+        let harsh = Harsh::builder().length(12).build().unwrap();
+        let id = harsh.encode(&[1, 2, 3]);
+        let report = harsh.decode_detailed(&id);
+
+        assert_eq!(Ok(vec![1, 2, 3]), report.result);
+        assert!(report.left_guard.is_some());
+        assert!(report.right_guard.is_some());
+        assert_eq!(3, report.segments.len());
+        assert!(report.segments[..2].iter().all(|s| s.separator.is_some()));
+        assert_eq!(None, report.segments[2].separator);
+
+        let mut rebuilt = report.left_padding.clone();
+        rebuilt.extend(report.left_guard);
+        rebuilt.extend(report.lottery);
+        for segment in &report.segments {
+            rebuilt.push_str(&segment.text);
+            rebuilt.extend(segment.separator);
+        }
+        rebuilt.extend(report.right_guard);
+        rebuilt.push_str(&report.right_padding);
+        assert_eq!(id, rebuilt);
+    }
+
+    #[test]
+    fn reports_failed_stage() {
+        let harsh = Harsh::default();
+
+        assert_eq!(Err(DecodeStage::Guards), harsh.decode_detailed("a").result);
+        assert_eq!(
+            Err(DecodeStage::Unhash { segment: 0 }),
+            harsh.decode_detailed("o2!XhV").result
+        );
+        assert_eq!(
+            Err(DecodeStage::Overflow { segment: 0 }),
+            harsh.decode_detailed(&"o".repeat(40)).result
+        );
+
+        let harsh = Harsh::builder().length(8).build().unwrap();
+        let id = harsh.encode(&[1, 2, 3]);
+        assert!(harsh.decode_detailed(&id).right_guard.is_some());
+        assert_eq!(
+            Err(DecodeStage::Canonical {
+                expected: id.clone()
+            }),
+            harsh.decode_detailed(&id[..id.len() - 1]).result
+        );
+    }
+
+    #[test]
+    fn value_count_is_checked_before_segments() {
+        let harsh = Harsh::builder().max_values(2).build().unwrap();
+        let id = Harsh::default().encode(&[1, 2, 3]);
+        let report = harsh.decode_detailed(&id);
+
+        assert_eq!(
+            Err(DecodeStage::Limit(DecodeError::ValueCount)),
+            report.result
+        );
+        assert!(report.segments.is_empty());
+    }
+
+    #[test]
+    fn agrees_with_decode() {
+        let harsh = Harsh::builder()
+            .salt("salt")
+            .length(10)
+            .max_values(4)
+            .build()
+            .unwrap();
+        let id = harsh.encode(&[5, 8, 13]);
+        let mut inputs = vec![id.clone(), String::new(), "xxxxxxxxxx".to_owned()];
+        for idx in 0..id.len() {
+            let mut typo = id.clone();
+            typo.remove(idx);
+            inputs.push(typo);
+        }
+
+        for input in &inputs {
+            let report = harsh.decode_detailed(input);
+            assert_eq!(
+                harsh.decode(input).map_err(|e| e.to_string()),
+                report.result.map_err(|stage| stage.error().to_string()),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(
+            DecodeError::Overflow,
+            DecodeStage::Overflow { segment: 0 }.error()
+        );
+    }
+}
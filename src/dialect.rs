@@ -0,0 +1,176 @@
+use crate::{
+    capacity,
+    harsh::{self, DecodeError},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The smallest value a `f64` cannot carry back into a `u64`, `2^64`.
+const U64_LIMIT: f64 = 18_446_744_073_709_551_616.0;
+
+/// The version of hashids.js whose behavior a `Harsh` reproduces.
+///
+/// Only hashids.js is supported as a reference. Both dialects derive the same
+/// alphabet, separators and guards; they encode differently only for values
+/// above `2^53`.
+///
+/// Use [`HarshBuilder::dialect`](./struct.HarshBuilder.html#method.dialect)
+/// to select a dialect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Dialect {
+    /// hashids.js 2.x, which computes with `BigInt` and so encodes every
+    /// `u64` exactly. This is the default.
+    #[default]
+    HashidsJs,
+
+    /// hashids.js 1.x, which computes with floating point numbers.
+    ///
+    /// Values above `2^53` are rounded to the nearest `f64` before they are
+    /// encoded, as they would be when passed to hashids.js as a `Number`,
+    /// and are hashed by floating point division. Ids decode to the rounded
+    /// values. Decoding sums the digits in floating point too, so ids of
+    /// values close to `2^64` decode to `2^64` and are rejected, as they are
+    /// by hashids.js. Keyed wrappers and generators that would produce values
+    /// above `2^53` refuse configurations using this dialect.
+    Legacy,
+}
+
+impl Dialect {
+    /// The sum of the values modulo their position, which selects the
+    /// lottery character and the guards.
+    pub(crate) fn nhash(self, values: &[u64]) -> u64 {
+        match self {
+            Dialect::HashidsJs => harsh::create_nhash(values),
+            Dialect::Legacy => values.iter().enumerate().fold(0u64, |a, (idx, &value)| {
+                let modulus = (idx as u64).wrapping_add(100) as f64;
+                a.wrapping_add((value as f64 % modulus) as u64)
+            }),
+        }
+    }
+
+    /// Hashes a single value into the alphabet.
+    pub(crate) fn hash(self, value: u64, alphabet: &[u8]) -> Vec<u8> {
+        match self {
+            Dialect::HashidsJs => harsh::hash(value, alphabet),
+            Dialect::Legacy => {
+                let length = alphabet.len() as f64;
+                let mut value = value as f64;
+                let mut hash = Vec::new();
+
+                loop {
+                    hash.push(alphabet[(value % length) as usize]);
+                    value = (value / length).trunc();
+
+                    if value == 0.0 {
+                        hash.reverse();
+                        return hash;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The width in bits of the values this dialect encodes exactly.
+    pub(crate) fn exact_bits(self) -> u32 {
+        match self {
+            Dialect::HashidsJs => 64,
+            Dialect::Legacy => 53,
+        }
+    }
+
+    /// The number of characters `hash` produces for `value` with an
+    /// alphabet of `base` characters.
+    pub(crate) fn digits(self, value: u64, base: usize) -> usize {
+        match self {
+            Dialect::HashidsJs => capacity::digits(value, base),
+            Dialect::Legacy => {
+                let base = base as f64;
+                let mut value = value as f64;
+                let mut digits = 1;
+
+                loop {
+                    value = (value / base).trunc();
+
+                    if value == 0.0 {
+                        return digits;
+                    }
+                    digits += 1;
+                }
+            }
+        }
+    }
+
+    /// Selects the separator following a value whose hash starts with
+    /// `first`.
+    pub(crate) fn separator_index(self, value: u64, first: u8, idx: usize, count: usize) -> usize {
+        let modulus = (first as usize + idx) as u64;
+        match self {
+            Dialect::HashidsJs => (value % modulus % count as u64) as usize,
+            Dialect::Legacy => (value as f64 % modulus as f64 % count as f64) as usize,
+        }
+    }
+
+    /// Parses a single value, failing in the same cases as `harsh::unhash`.
+    pub(crate) fn unhash(self, input: &[u8], alphabet: &[u8]) -> Result<u64, DecodeError> {
+        match self {
+            Dialect::HashidsJs => harsh::unhash(input, alphabet),
+            Dialect::Legacy => {
+                let length = alphabet.len() as f64;
+                let value = input.iter().enumerate().try_fold(0.0, |a, (idx, &value)| {
+                    let pos = alphabet
+                        .iter()
+                        .position(|&item| item == value)
+                        .ok_or(DecodeError::Value)?;
+                    Ok(a + pos as f64 * length.powf((input.len() - idx - 1) as f64))
+                })?;
+
+                // Also rejects the NaN left by a zero digit times an infinite
+                // power.
+                if value < U64_LIMIT {
+                    Ok(value as u64)
+                } else {
+                    Err(DecodeError::Overflow)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dialect;
+
+    #[test]
+    fn legacy_matches_below_two_to_the_53() {
+        let alphabet = b"abcdefghijklmnopqrstuvwxyz";
+        for &value in &[0, 1, 25, 26, 12345, (1 << 53) - 1, 1 << 53] {
+            let hash = Dialect::HashidsJs.hash(value, alphabet);
+            assert_eq!(hash, Dialect::Legacy.hash(value, alphabet));
+            assert_eq!(Ok(value), Dialect::Legacy.unhash(&hash, alphabet));
+        }
+    }
+
+    #[test]
+    fn legacy_rounds_large_values() {
+        let alphabet = b"abcdefghijklmnopqrstuvwxyz";
+        assert_eq!(
+            Dialect::Legacy.hash(1 << 53, alphabet),
+            Dialect::Legacy.hash((1 << 53) + 1, alphabet)
+        );
+        assert_ne!(
+            Dialect::HashidsJs.hash(u64::MAX, alphabet),
+            Dialect::Legacy.hash(u64::MAX, alphabet)
+        );
+
+        let base = alphabet.len();
+        assert_eq!(13, Dialect::HashidsJs.digits(26u64.pow(13) - 1, base));
+        assert_eq!(14, Dialect::Legacy.digits(26u64.pow(13) - 1, base));
+        assert_eq!(14, Dialect::Legacy.hash(26u64.pow(13) - 1, alphabet).len());
+
+        let hash = Dialect::Legacy.hash(u64::MAX, alphabet);
+        assert!(Dialect::Legacy.unhash(&hash, alphabet).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{dialect::Dialect, harsh::Harsh};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// The version of the fingerprint format, bumped whenever the digested
+/// fields or their encoding change.
+const FINGERPRINT_VERSION: u32 = 2;
+
+impl Harsh {
+    /// Returns a stable digest of the effective configuration.
+    ///
+    /// The fingerprint covers the derived alphabet, separators and guards,
+    /// the salt, the hash length and every other option affecting which ids
+    /// are produced or accepted, so two instances with equal fingerprints
+    /// encode and decode identically. The salt is only included in hashed
+    /// form, but a guessable salt may still be recovered by brute force.
+    ///
+    /// The format is `v<version>:<hex digest>`; the version changes only when
+    /// the fingerprint of an unchanged configuration would change.
+    ///
+    /// Available with the `fingerprint` feature enabled.
+    pub fn fingerprint(&self) -> String {
+        let mut digest = Sha256::new();
+        digest.update(b"harsh-fingerprint");
+        digest.update(FINGERPRINT_VERSION.to_be_bytes());
+
+        field(&mut digest, &self.alphabet);
+        field(&mut digest, &self.separators);
+        field(&mut digest, &self.guards);
+        field(&mut digest, &self.salt);
+        field(&mut digest, &(self.hash_length as u64).to_be_bytes());
+        field(&mut digest, &[self.exact_length as u8]);
+        field(&mut digest, self.version_tag().as_slice());
+        field(&mut digest, self.prefix().unwrap_or_default().as_bytes());
+        field(&mut digest, self.check.as_deref().unwrap_or_default());
+        field(
+            &mut digest,
+            match self.dialect {
+                Dialect::HashidsJs => b"hashids.js",
+                Dialect::Legacy => b"legacy",
+            },
+        );
+
+        let normalization: Vec<_> = self
+            .normalization
+            .iter()
+            .flat_map(|&(from, to)| vec![from, to])
+            .collect();
+        field(&mut digest, &normalization);
+
+        for limit in &[
+            self.limits.input_length.map(|n| n as u64),
+            self.limits.values.map(|n| n as u64),
+            self.limits.value,
+        ] {
+            match limit {
+                None => field(&mut digest, &[]),
+                Some(n) => field(&mut digest, &n.to_be_bytes()),
+            }
+        }
+
+        let mut fingerprint = format!("v{}:", FINGERPRINT_VERSION);
+        for byte in digest.finalize() {
+            write!(fingerprint, "{:02x}", byte).unwrap();
+        }
+        fingerprint
+    }
+}
+
+/// Adds a length-prefixed field, so that no two configurations share input.
+fn field(digest: &mut Sha256, bytes: &[u8]) {
+    digest.update((bytes.len() as u64).to_be_bytes());
+    digest.update(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dialect, Harsh};
+    use std::collections::HashSet;
+
+    #[test]
+    fn fingerprint_is_stable() {
+        assert_eq!(
+            "v2:73c30505af46fb553676f4f4e65e01d503f9516d8f9d6c0e5ac9a12e6bdd6dae",
+            Harsh::default().fingerprint()
+        );
+    }
+
+    #[test]
+    fn fingerprint_follows_configuration() {
+        let a = Harsh::builder().salt("this is my salt").build().unwrap();
+        let b = Harsh::builder().salt("this is my salt").build().unwrap();
+        let c = Harsh::builder().salt("this is my pepper").build().unwrap();
+        let d = Harsh::builder()
+            .salt("this is my salt")
+            .length(8)
+            .build()
+            .unwrap();
+        let e = Harsh::builder()
+            .salt("this is my salt")
+            .dialect(Dialect::Legacy)
+            .build()
+            .unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a, c);
+        assert_ne!(a.fingerprint(), c.fingerprint());
+        assert_ne!(a, d);
+        assert_ne!(a.fingerprint(), d.fingerprint());
+        assert_ne!(a, e);
+        assert_ne!(a.fingerprint(), e.fingerprint());
+        assert!(!a.fingerprint().contains("salt"));
+
+        let set: HashSet<_> = vec![a, b, c, d, e].into_iter().collect();
+        assert_eq!(4, set.len());
+    }
+}
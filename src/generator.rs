@@ -0,0 +1,415 @@
+use crate::{
+    harsh::{EncodeError, Harsh, HarshError},
+    layout::{IdLayout, LayoutError},
+};
+use std::{
+    convert::TryFrom,
+    error, fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The default epoch, 2020-01-01T00:00:00Z in milliseconds since the Unix epoch.
+const DEFAULT_EPOCH: u64 = 1_577_836_800_000;
+const DEFAULT_BITS: (u32, u32, u32) = (41, 10, 12);
+
+/// Represents errors encountered while building or running a `Generator`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GeneratorError {
+    /// Error returned when the bit layout is invalid
+    Layout(LayoutError),
+
+    /// Error returned when the worker id does not fit its field
+    Worker(u64),
+
+    /// Error returned when the clock reads a time before the epoch
+    Clock(u64),
+
+    /// Error returned when the timestamp no longer fits its field
+    Exhausted,
+
+    /// Error returned when the packed id cannot be encoded
+    Encode(EncodeError),
+
+    /// Error returned when the layout is wider than the configuration's
+    /// dialect encodes exactly
+    Dialect,
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        static WORKER_MESSAGE: &str = "The worker id does not fit the worker field";
+        static CLOCK_MESSAGE: &str = "The clock reads a time before the generator epoch";
+        static EXHAUSTED_MESSAGE: &str = "The timestamp no longer fits the timestamp field";
+        static DIALECT_MESSAGE: &str =
+            "The configured dialect cannot encode ids of this width exactly";
+
+        match self {
+            GeneratorError::Layout(e) => e.fmt(f),
+            GeneratorError::Worker(worker) => write!(f, "{} ({})", WORKER_MESSAGE, worker),
+            GeneratorError::Clock(now) => write!(f, "{} ({})", CLOCK_MESSAGE, now),
+            GeneratorError::Exhausted => write!(f, "{}", EXHAUSTED_MESSAGE),
+            GeneratorError::Encode(e) => e.fmt(f),
+            GeneratorError::Dialect => write!(f, "{}", DIALECT_MESSAGE),
+        }
+    }
+}
+
+impl error::Error for GeneratorError {}
+
+impl From<LayoutError> for GeneratorError {
+    fn from(e: LayoutError) -> Self {
+        GeneratorError::Layout(e)
+    }
+}
+
+impl From<EncodeError> for GeneratorError {
+    fn from(e: EncodeError) -> Self {
+        GeneratorError::Encode(e)
+    }
+}
+
+/// A source of wall-clock time for a `Generator`.
+pub trait Clock {
+    /// The current time in milliseconds since the Unix epoch.
+    fn now(&self) -> u64;
+}
+
+/// The system clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX))
+            .unwrap_or(0)
+    }
+}
+
+/// A clock that only moves when told to, for tests.
+#[derive(Debug, Default)]
+pub struct MockClock(AtomicU64);
+
+impl MockClock {
+    /// Creates a clock reading `now` milliseconds since the Unix epoch.
+    pub fn new(now: u64) -> MockClock {
+        MockClock(AtomicU64::new(now))
+    }
+
+    /// Sets the time, which may move the clock backwards.
+    pub fn set(&self, now: u64) {
+        self.0.store(now, Ordering::SeqCst);
+    }
+
+    /// Moves the clock forwards by `millis` milliseconds.
+    pub fn advance(&self, millis: u64) {
+        self.0.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// The fields of an id produced by a `Generator`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GeneratedId {
+    /// The time the id was generated, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The worker that generated the id.
+    pub worker: u64,
+    /// The position of the id among those generated in the same millisecond.
+    pub sequence: u64,
+}
+
+/// A builder for a `Generator`.
+#[derive(Debug)]
+pub struct GeneratorBuilder<C = SystemClock> {
+    harsh: Harsh,
+    clock: C,
+    epoch: u64,
+    worker: u64,
+    bits: (u32, u32, u32),
+}
+
+impl GeneratorBuilder {
+    /// Creates a builder encoding ids with `harsh`.
+    pub fn new(harsh: Harsh) -> GeneratorBuilder {
+        GeneratorBuilder {
+            harsh,
+            clock: SystemClock,
+            epoch: DEFAULT_EPOCH,
+            worker: 0,
+            bits: DEFAULT_BITS,
+        }
+    }
+}
+
+impl<C: Clock> GeneratorBuilder<C> {
+    /// Sets the epoch in milliseconds since the Unix epoch.
+    ///
+    /// Timestamps are stored relative to the epoch, so a recent epoch makes
+    /// the timestamp field last longer. The default is the start of 2020.
+    pub fn epoch(mut self, epoch: u64) -> GeneratorBuilder<C> {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Sets the id of this worker, which must be unique among the generators
+    /// sharing a layout.
+    pub fn worker(mut self, worker: u64) -> GeneratorBuilder<C> {
+        self.worker = worker;
+        self
+    }
+
+    /// Sets the widths of the timestamp, worker and sequence fields.
+    ///
+    /// The default of 41, 10 and 12 bits allows 1024 workers to generate
+    /// 4096 ids per millisecond each for roughly 69 years.
+    pub fn bits(mut self, timestamp: u32, worker: u32, sequence: u32) -> GeneratorBuilder<C> {
+        self.bits = (timestamp, worker, sequence);
+        self
+    }
+
+    /// Sets the clock, typically to a `MockClock` in tests.
+    pub fn clock<D: Clock>(self, clock: D) -> GeneratorBuilder<D> {
+        GeneratorBuilder {
+            harsh: self.harsh,
+            clock,
+            epoch: self.epoch,
+            worker: self.worker,
+            bits: self.bits,
+        }
+    }
+
+    /// Builds the generator, failing if the layout is invalid, the worker
+    /// id does not fit the worker field, or the layout is wider than 53 bits
+    /// and `harsh` uses `Dialect::Legacy`, which rounds larger values.
+    pub fn build(self) -> Result<Generator<C>, GeneratorError> {
+        let (timestamp, worker, sequence) = self.bits;
+        let layout = IdLayout::new(&[
+            ("timestamp", timestamp),
+            ("worker", worker),
+            ("sequence", sequence),
+        ])?;
+
+        if layout.pack(&[0, self.worker, 0]).is_err() {
+            return Err(GeneratorError::Worker(self.worker));
+        }
+        if layout.width() > self.harsh.dialect.exact_bits() {
+            return Err(GeneratorError::Dialect);
+        }
+
+        Ok(Generator {
+            harsh: self.harsh,
+            layout,
+            clock: self.clock,
+            epoch: self.epoch,
+            worker: self.worker,
+            state: Mutex::new(None),
+        })
+    }
+}
+
+/// A generator of unique, time-ordered ids, encoded as hashids.
+///
+/// Each id packs a timestamp, a worker id and a sequence number into a
+/// single `u64` using an `IdLayout`, with the timestamp most significant.
+/// The packed values a generator produces strictly increase, even if the
+/// clock moves backwards or more ids are requested in one millisecond than
+/// the sequence field can count; in both cases the generator carries on from
+/// the last timestamp it used. Note that the encoded hashids themselves do
+/// not sort in the same order.
+///
+/// ```rust
+/// # use harsh::{Generator, Harsh, MockClock};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let generator = Generator::builder(Harsh::default())
+///     .worker(7)
+///     .clock(MockClock::new(1_600_000_000_000))
+///     .build()?;
+///
+/// let id = generator.generate()?;
+/// let parsed = generator.parse(&id)?;
+///
+/// assert_eq!(1_600_000_000_000, parsed.timestamp);
+/// assert_eq!(7, parsed.worker);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Generator<C = SystemClock> {
+    harsh: Harsh,
+    layout: IdLayout,
+    clock: C,
+    epoch: u64,
+    worker: u64,
+    state: Mutex<Option<(u64, u64)>>,
+}
+
+impl Generator {
+    /// Creates a builder encoding ids with `harsh`.
+    pub fn builder(harsh: Harsh) -> GeneratorBuilder {
+        GeneratorBuilder::new(harsh)
+    }
+}
+
+impl<C: Clock> Generator<C> {
+    /// The layout used to pack ids.
+    pub fn layout(&self) -> &IdLayout {
+        &self.layout
+    }
+
+    /// The clock used to timestamp ids.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Generates a new id, packed but not encoded.
+    pub fn next_value(&self) -> Result<u64, GeneratorError> {
+        let now = self.clock.now();
+        let elapsed = now
+            .checked_sub(self.epoch)
+            .ok_or(GeneratorError::Clock(now))?;
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (timestamp, sequence) = match *state {
+            Some((last, sequence)) if elapsed <= last => (last, sequence + 1),
+            _ => (elapsed, 0),
+        };
+
+        let packed = match self.layout.pack(&[timestamp, self.worker, sequence]) {
+            Ok(packed) => packed,
+            Err(EncodeError::Range(2)) => {
+                let timestamp = timestamp.checked_add(1).ok_or(GeneratorError::Exhausted)?;
+                let packed = self
+                    .layout
+                    .pack(&[timestamp, self.worker, 0])
+                    .map_err(|_| GeneratorError::Exhausted)?;
+                *state = Some((timestamp, 0));
+                return Ok(packed);
+            }
+            Err(_) => return Err(GeneratorError::Exhausted),
+        };
+
+        *state = Some((timestamp, sequence));
+        Ok(packed)
+    }
+
+    /// Generates a new id and encodes it.
+    pub fn generate(&self) -> Result<String, GeneratorError> {
+        Ok(self.harsh.try_encode(&[self.next_value()?])?)
+    }
+
+    /// Decodes an id produced by a generator with the same configuration.
+    pub fn parse<T: AsRef<str>>(&self, input: T) -> Result<GeneratedId, HarshError> {
+        let fields = self.harsh.decode_packed(&self.layout, input)?;
+
+        Ok(GeneratedId {
+            timestamp: fields[0].saturating_add(self.epoch),
+            worker: fields[1],
+            sequence: fields[2],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Generator, GeneratorError, MockClock};
+    use crate::{Dialect, Harsh, LayoutError};
+
+    const EPOCH: u64 = 1_000_000;
+
+    fn generator(worker: u64, bits: (u32, u32, u32)) -> Generator<MockClock> {
+        Generator::builder(Harsh::default())
+            .epoch(EPOCH)
+            .worker(worker)
+            .bits(bits.0, bits.1, bits.2)
+            .clock(MockClock::new(EPOCH + 5))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn ids_round_trip() {
+        let generator = generator(3, (41, 10, 12));
+        let first = generator.generate().unwrap();
+        let second = generator.generate().unwrap();
+
+        let parsed = generator.parse(&first).unwrap();
+        assert_eq!(
+            (EPOCH + 5, 3, 0),
+            (parsed.timestamp, parsed.worker, parsed.sequence)
+        );
+        assert_eq!(1, generator.parse(&second).unwrap().sequence);
+
+        generator.clock().advance(10);
+        let parsed = generator.parse(generator.generate().unwrap()).unwrap();
+        assert_eq!((EPOCH + 15, 0), (parsed.timestamp, parsed.sequence));
+    }
+
+    #[test]
+    fn values_increase_when_clock_goes_backwards() {
+        let generator = generator(1, (20, 2, 2));
+        let mut previous = generator.next_value().unwrap();
+
+        generator.clock().set(EPOCH + 1);
+        for _ in 0..10 {
+            let next = generator.next_value().unwrap();
+            assert!(next > previous);
+            previous = next;
+        }
+
+        generator.clock().set(EPOCH - 1);
+        assert_eq!(
+            Err(GeneratorError::Clock(EPOCH - 1)),
+            generator.next_value()
+        );
+    }
+
+    #[test]
+    fn sequence_overflow_borrows_next_millisecond() {
+        let generator = generator(0, (4, 1, 1));
+        let values: Vec<_> = (0..4).map(|_| generator.next_value().unwrap()).collect();
+        let timestamps: Vec<_> = values
+            .iter()
+            .map(|&v| generator.layout().get(v, "timestamp").unwrap())
+            .collect();
+        assert_eq!(vec![5, 5, 6, 6], timestamps);
+
+        generator.clock().set(EPOCH + 15);
+        generator.next_value().unwrap();
+        generator.next_value().unwrap();
+        assert_eq!(Err(GeneratorError::Exhausted), generator.next_value());
+    }
+
+    #[test]
+    fn invalid_configurations() {
+        let builder = || Generator::builder(Harsh::default());
+
+        assert_eq!(
+            GeneratorError::Worker(1024),
+            builder().worker(1024).build().unwrap_err()
+        );
+        assert_eq!(
+            GeneratorError::Layout(LayoutError::Width(65)),
+            builder().bits(43, 10, 12).build().unwrap_err()
+        );
+
+        let legacy = || {
+            let harsh = Harsh::builder().dialect(Dialect::Legacy).build().unwrap();
+            Generator::builder(harsh)
+        };
+        assert_eq!(GeneratorError::Dialect, legacy().build().unwrap_err());
+        assert!(legacy().bits(31, 10, 12).build().is_ok());
+    }
+}
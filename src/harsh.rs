@@ -1,18 +1,65 @@
-use crate::{builder::HarshBuilder, shuffle};
-use std::{error, fmt, result, str};
+use crate::{
+    builder::HarshBuilder,
+    capacity,
+    diagnostics::{DecodeReport, DecodeStage, Segment},
+    dialect::Dialect,
+    shuffle,
+    trace::TraceStep,
+};
+use std::{borrow::Cow, convert::TryFrom, error, fmt, result, str};
 
 type Result<T, E = HarshError> = result::Result<T, E>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum HarshError {
     Hex,
+    Encode(EncodeError),
     Decode(DecodeError),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EncodeError {
+    Length,
+    Empty,
+    Internal,
+    Range(usize),
+    Arity(usize),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncodeError::Length => f.write_str("Values do not fit the exact hash length"),
+            EncodeError::Empty => f.write_str("No values to encode"),
+            EncodeError::Internal => f.write_str("Configuration produced an invalid hashid"),
+            EncodeError::Range(index) => {
+                write!(f, "Value out of range for a hashid (at {})", index)
+            }
+            EncodeError::Arity(found) => write!(f, "Unexpected number of values ({})", found),
+        }
+    }
+}
+
+impl error::Error for EncodeError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum DecodeError {
     Value,
     Hash,
+    Version,
+    Prefix,
+    Check,
+    InputLength,
+    ValueCount,
+    ValueMagnitude,
+    Overflow,
+    Arity(usize),
+    Range(usize),
+    Signature,
+    Layout,
 }
 
 impl fmt::Display for DecodeError {
@@ -20,6 +67,25 @@ impl fmt::Display for DecodeError {
         match self {
             DecodeError::Value => f.write_str("Found bad value"),
             DecodeError::Hash => f.write_str("Malformed hashid"),
+            DecodeError::Version => f.write_str("Missing or unexpected version tag"),
+            DecodeError::Prefix => f.write_str("Missing or unexpected prefix"),
+            DecodeError::Check => f.write_str("Check character mismatch"),
+            DecodeError::InputLength => f.write_str("Hashid exceeds the maximum input length"),
+            DecodeError::ValueCount => f.write_str("Hashid exceeds the maximum number of values"),
+            DecodeError::ValueMagnitude => f.write_str("Hashid contains a value above the maximum"),
+            DecodeError::Overflow => f.write_str("Hashid contains a value too large for a u64"),
+            DecodeError::Arity(found) => {
+                write!(
+                    f,
+                    "Hashid contains an unexpected number of values ({})",
+                    found
+                )
+            }
+            DecodeError::Range(index) => {
+                write!(f, "Value out of range for the target type (at {})", index)
+            }
+            DecodeError::Signature => f.write_str("Missing or invalid signature"),
+            DecodeError::Layout => f.write_str("Packed value is wider than the layout"),
         }
     }
 }
@@ -30,18 +96,29 @@ impl fmt::Display for HarshError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             HarshError::Hex => f.write_str("Failed to decode hex value"),
-            HarshError::Decode(e) => match e {
-                DecodeError::Value => f.write_str("Found bad value"),
-                DecodeError::Hash => f.write_str("Malformed hashid"),
-            },
+            HarshError::Encode(e) => e.fmt(f),
+            HarshError::Decode(e) => e.fmt(f),
         }
     }
 }
 
+impl From<EncodeError> for HarshError {
+    fn from(e: EncodeError) -> HarshError {
+        HarshError::Encode(e)
+    }
+}
+
+impl From<DecodeError> for HarshError {
+    fn from(e: DecodeError) -> HarshError {
+        HarshError::Decode(e)
+    }
+}
+
 impl error::Error for HarshError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             HarshError::Hex => None,
+            HarshError::Encode(ref e) => Some(e),
             HarshError::Decode(ref e) => Some(e),
         }
     }
@@ -52,13 +129,28 @@ impl error::Error for HarshError {
 /// It's probably not a great idea to use the default, because in that case
 /// your values will be entirely trivial to decode. On the other hand, this is
 /// not intended to be cryptographically-secure, so go nuts!
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Harsh {
-    alphabet: Box<[u8]>,
-    guards: Box<[u8]>,
-    hash_length: usize,
-    salt: Box<[u8]>,
-    separators: Box<[u8]>,
+    pub(crate) alphabet: Box<[u8]>,
+    pub(crate) guards: Box<[u8]>,
+    pub(crate) hash_length: usize,
+    pub(crate) exact_length: bool,
+    pub(crate) salt: Box<[u8]>,
+    pub(crate) separators: Box<[u8]>,
+    pub(crate) version: Option<(u8, u8)>,
+    pub(crate) prefix: Option<Box<str>>,
+    pub(crate) check: Option<Box<[u8]>>,
+    pub(crate) normalization: Box<[(u8, u8)]>,
+    pub(crate) limits: Limits,
+    pub(crate) dialect: Dialect,
+}
+
+/// Limits enforced on decoder input before any values are parsed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Limits {
+    pub(crate) input_length: Option<usize>,
+    pub(crate) values: Option<usize>,
+    pub(crate) value: Option<u64>,
 }
 
 impl Harsh {
@@ -74,70 +166,139 @@ impl Harsh {
         HarshBuilder::new()
     }
 
-    pub(crate) fn initialize(
-        alphabet: Box<[u8]>,
-        guards: Box<[u8]>,
-        hash_length: usize,
-        salt: Box<[u8]>,
-        separators: Box<[u8]>,
-    ) -> Self {
-        Harsh {
-            alphabet,
-            guards,
-            hash_length,
-            salt,
-            separators,
-        }
+    /// The alphabet used to hash values, after separators and guards have
+    /// been removed and the remainder shuffled by the salt.
+    pub fn alphabet(&self) -> &[u8] {
+        &self.alphabet
+    }
+
+    /// The separators placed between hashed values.
+    pub fn separators(&self) -> &[u8] {
+        &self.separators
+    }
+
+    /// The guards used to pad hashids up to the minimum length.
+    pub fn guards(&self) -> &[u8] {
+        &self.guards
+    }
+
+    /// The minimum (or, with `HarshBuilder::exact_length`, exact) hash length.
+    pub fn min_length(&self) -> usize {
+        self.hash_length
+    }
+
+    /// The configuration version, if one was provided.
+    pub fn version(&self) -> Option<u8> {
+        self.version.map(|(version, _)| version)
+    }
+
+    /// The reference implementation whose behavior this instance reproduces.
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// The prefix rendered in front of hashids, if one was provided.
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// The tag character prefixed to hashids of a versioned configuration.
+    pub(crate) fn version_tag(&self) -> Option<u8> {
+        self.version.map(|(_, tag)| tag)
     }
 
     /// Encodes a slice of `u64` values into a single hashid.
+    ///
+    /// Returns an empty string if the values cannot be encoded, which is the
+    /// case for empty input or for values exceeding an exact hash length.
+    /// Use `try_encode` to tell these cases apart.
     pub fn encode(&self, values: &[u64]) -> String {
+        self.try_encode(values).unwrap_or_default()
+    }
+
+    /// Encodes a slice of `u64` values into a single hashid, failing with
+    /// `EncodeError::Empty` if there are no values, or with
+    /// `EncodeError::Length` if an exact hash length was configured and the
+    /// values do not fit.
+    pub fn try_encode(&self, values: &[u64]) -> Result<String, EncodeError> {
+        self.encode_with(values, &mut None)
+    }
+
+    /// Encodes `values`, recording each intermediate state in `trace` if one
+    /// is given.
+    pub(crate) fn encode_with(
+        &self,
+        values: &[u64],
+        trace: &mut Option<&mut Vec<TraceStep>>,
+    ) -> Result<String, EncodeError> {
         if values.is_empty() {
-            return String::new();
+            return Err(EncodeError::Empty);
         }
 
-        let nhash = create_nhash(values);
+        let nhash = self.dialect.nhash(values);
+        record(trace, || TraceStep::Nhash { value: nhash });
 
         let mut alphabet = self.alphabet.clone();
-        let mut buffer = String::new();
+        let mut buffer = Vec::new();
 
         let idx = (nhash % alphabet.len() as u64) as usize;
         let lottery = alphabet[idx];
-        buffer.push(lottery as char);
+        buffer.push(lottery);
+        record(trace, || TraceStep::Lottery {
+            index: idx,
+            character: lottery as char,
+        });
 
         for (idx, &value) in values.iter().enumerate() {
-            let mut value = value;
-            let mut temp = Vec::with_capacity(self.salt.len() + alphabet.len() + 1);
-            temp.push(lottery);
-            temp.extend_from_slice(&self.salt);
-            temp.extend_from_slice(&alphabet);
-
-            let alphabet_len = alphabet.len();
-            shuffle(&mut alphabet, &temp[..alphabet_len]);
-
-            let last = hash(value, &alphabet);
-            buffer.push_str(&last);
+            self.shuffle_alphabet(&mut alphabet, lottery);
+            record(trace, || TraceStep::Shuffle {
+                alphabet: String::from_utf8_lossy(&alphabet).into_owned(),
+            });
+
+            let last = self.dialect.hash(value, &alphabet);
+            buffer.extend_from_slice(&last);
+            record(trace, || TraceStep::Hash {
+                value,
+                output: String::from_utf8_lossy(&last).into_owned(),
+            });
 
             if idx + 1 < values.len() {
-                value %= (last.bytes().next().unwrap_or(0) as usize + idx) as u64;
-                buffer
-                    .push(self.separators[(value % self.separators.len() as u64) as usize] as char);
+                let separator_index =
+                    self.dialect
+                        .separator_index(value, last[0], idx, self.separators.len());
+                let separator = self.separators[separator_index];
+                buffer.push(separator);
+                record(trace, || TraceStep::Separator {
+                    index: separator_index,
+                    character: separator as char,
+                });
             }
         }
 
+        if self.exact_length && buffer.len() > self.hash_length {
+            return Err(EncodeError::Length);
+        }
+
         if buffer.len() < self.hash_length {
-            let guard_index = (nhash as usize
-                + buffer.bytes().next().expect("hellfire and damnation") as usize)
-                % self.guards.len();
+            let guard_index = (nhash as usize).wrapping_add(lottery as usize) % self.guards.len();
             let guard = self.guards[guard_index];
-            buffer.insert(0, guard as char);
+            buffer.insert(0, guard);
+            record(trace, || TraceStep::LeftGuard {
+                index: guard_index,
+                character: guard as char,
+            });
 
             if buffer.len() < self.hash_length {
-                let guard_index = (nhash as usize
-                    + buffer.bytes().nth(2).expect("hellfire and damnation") as usize)
-                    % self.guards.len();
+                // The first character of the first value, following the
+                // guard and the lottery character.
+                let guard_index =
+                    (nhash as usize).wrapping_add(buffer[2] as usize) % self.guards.len();
                 let guard = self.guards[guard_index];
-                buffer.push(guard as char);
+                buffer.push(guard);
+                record(trace, || TraceStep::RightGuard {
+                    index: guard_index,
+                    character: guard as char,
+                });
             }
         }
 
@@ -149,72 +310,236 @@ impl Harsh {
             }
 
             let (left, right) = alphabet.split_at(half_length);
-            buffer = format!(
-                "{}{}{}",
-                String::from_utf8_lossy(right),
-                buffer,
-                String::from_utf8_lossy(left)
-            );
-
-            let excess = buffer.len() as i32 - self.hash_length as i32;
-            if excess > 0 {
-                let marker = excess as usize / 2;
-                buffer = buffer[marker..marker + self.hash_length].to_owned();
+            buffer = [right, &buffer, left].concat();
+
+            if buffer.len() > self.hash_length {
+                let marker = (buffer.len() - self.hash_length) / 2;
+                buffer = buffer[marker..marker + self.hash_length].to_vec();
             }
+
+            record(trace, || TraceStep::Padding {
+                alphabet: String::from_utf8_lossy(&alphabet).into_owned(),
+                output: String::from_utf8_lossy(&buffer).into_owned(),
+            });
+        }
+
+        if let Some(tag) = self.version_tag() {
+            buffer.insert(0, tag);
+            record(trace, || TraceStep::Version {
+                character: tag as char,
+            });
         }
 
-        buffer
+        if let Some(check_alphabet) = &self.check {
+            let check = check_character(&buffer, check_alphabet).ok_or(EncodeError::Internal)?;
+            buffer.push(check);
+            record(trace, || TraceStep::Check {
+                character: check as char,
+            });
+        }
+
+        if let Some(prefix) = &self.prefix {
+            buffer.splice(0..0, prefix.bytes().chain(Some(b'_')));
+            record(trace, || TraceStep::Prefix {
+                prefix: prefix.to_string(),
+            });
+        }
+
+        String::from_utf8(buffer).map_err(|_| EncodeError::Internal)
     }
 
     /// Decodes a single hashid into a slice of `u64` values.
+    ///
+    /// Any limits configured on the builder are checked before the values
+    /// are parsed, failing with `DecodeError::InputLength`,
+    /// `DecodeError::ValueCount` or `DecodeError::ValueMagnitude`.
     pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<Vec<u64>> {
-        let mut value = input.as_ref().as_bytes();
+        self.decode_with(input.as_ref(), &mut None)
+            .map_err(|stage| HarshError::Decode(stage.error()))
+    }
+
+    /// Decodes a hashid, describing each step of the parse in `report` if
+    /// one is given.
+    pub(crate) fn decode_with(
+        &self,
+        input: &str,
+        report: &mut Option<&mut DecodeReport>,
+    ) -> result::Result<Vec<u64>, DecodeStage> {
+        if let Some(max) = self.limits.input_length {
+            if input.len() > max {
+                return Err(DecodeStage::Limit(DecodeError::InputLength));
+            }
+        }
+
+        let input = self.normalize(input);
+        if let Some(report) = report.as_deref_mut() {
+            report.input = input.clone().into_owned();
+        }
+
+        let mut value = self
+            .strip_decorations(input.as_bytes())
+            .map_err(DecodeStage::Decoration)?;
 
         if let Some(guard_idx) = value.iter().position(|u| self.guards.contains(u)) {
+            if let Some(report) = report.as_deref_mut() {
+                report.left_padding = String::from_utf8_lossy(&value[..guard_idx]).into_owned();
+                report.left_guard = Some(value[guard_idx] as char);
+            }
             value = &value[(guard_idx + 1)..];
         }
 
         if let Some(guard_idx) = value.iter().rposition(|u| self.guards.contains(u)) {
+            if let Some(report) = report.as_deref_mut() {
+                report.right_guard = Some(value[guard_idx] as char);
+                report.right_padding =
+                    String::from_utf8_lossy(&value[(guard_idx + 1)..]).into_owned();
+            }
             value = &value[..guard_idx];
         }
 
         if value.len() < 2 {
-            return Err(HarshError::Decode(DecodeError::Hash));
+            return Err(DecodeStage::Guards);
         }
 
         let mut alphabet = self.alphabet.clone();
 
         let lottery = value[0];
         let value = &value[1..];
+        if let Some(report) = report.as_deref_mut() {
+            report.lottery = Some(lottery as char);
+        }
+
+        if let Some(max) = self.limits.values {
+            if value.iter().filter(|u| self.separators.contains(u)).count() >= max {
+                return Err(DecodeStage::Limit(DecodeError::ValueCount));
+            }
+        }
+
         let segments: Vec<_> = value.split(|u| self.separators.contains(u)).collect();
 
-        let result: Option<Vec<_>> = segments
-            .into_iter()
-            .map(|segment| {
-                let mut buffer = Vec::with_capacity(self.salt.len() + alphabet.len() + 1);
-                buffer.push(lottery);
-                buffer.extend_from_slice(&self.salt);
-                buffer.extend_from_slice(&alphabet);
-
-                let alphabet_len = alphabet.len();
-                shuffle(&mut alphabet, &buffer[..alphabet_len]);
-                unhash(segment, &alphabet)
-            })
-            .collect();
+        if let Some(report) = report.as_deref_mut() {
+            let mut offset = 0;
+            for segment in &segments {
+                offset += segment.len();
+                report.segments.push(Segment {
+                    text: String::from_utf8_lossy(segment).into_owned(),
+                    separator: value.get(offset).map(|&u| u as char),
+                    value: None,
+                });
+                offset += 1;
+            }
+        }
+
+        if let Some(max) = self.limits.value {
+            let max_digits = self.dialect.digits(max, alphabet.len());
+            if segments.iter().any(|segment| segment.len() > max_digits) {
+                return Err(DecodeStage::Limit(DecodeError::ValueMagnitude));
+            }
+        }
+
+        let mut result = Vec::with_capacity(segments.len());
+        for (idx, segment) in segments.into_iter().enumerate() {
+            self.shuffle_alphabet(&mut alphabet, lottery);
+
+            let value = match self.dialect.unhash(segment, &alphabet) {
+                Ok(value) => value,
+                Err(DecodeError::Overflow) => return Err(DecodeStage::Overflow { segment: idx }),
+                Err(_) => return Err(DecodeStage::Unhash { segment: idx }),
+            };
+            if let Some(report) = report.as_deref_mut() {
+                report.segments[idx].value = Some(value);
+            }
+
+            if self.limits.value.is_some_and(|max| value > max) {
+                return Err(DecodeStage::Limit(DecodeError::ValueMagnitude));
+            }
+            result.push(value);
+        }
+
+        let expected = self.encode(&result);
+        if expected == input {
+            Ok(result)
+        } else {
+            Err(DecodeStage::Canonical { expected })
+        }
+    }
+
+    /// Strips the prefix, check character and version tag from a hashid,
+    /// verifying each of them.
+    pub(crate) fn strip_decorations<'a>(
+        &self,
+        mut value: &'a [u8],
+    ) -> result::Result<&'a [u8], DecodeError> {
+        if let Some(prefix) = &self.prefix {
+            value = value
+                .strip_prefix(prefix.as_bytes())
+                .and_then(|value| value.strip_prefix(b"_"))
+                .ok_or(DecodeError::Prefix)?;
+        }
 
-        match result {
-            None => Err(HarshError::Decode(DecodeError::Value)),
-            Some(result) => {
-                if self.encode(&result) == input.as_ref() {
-                    Ok(result)
-                } else {
-                    Err(HarshError::Decode(DecodeError::Hash))
+        if let Some(check_alphabet) = &self.check {
+            match value.split_last() {
+                Some((&check, rest)) if check_character(rest, check_alphabet) == Some(check) => {
+                    value = rest
                 }
+                _ => return Err(DecodeError::Check),
             }
         }
+
+        if let Some(tag) = self.version_tag() {
+            match value.split_first() {
+                Some((&first, rest)) if first == tag => value = rest,
+                _ => return Err(DecodeError::Version),
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Shuffles the alphabet ahead of hashing the next value.
+    pub(crate) fn shuffle_alphabet(&self, alphabet: &mut [u8], lottery: u8) {
+        let mut buffer = Vec::with_capacity(self.salt.len() + alphabet.len() + 1);
+        buffer.push(lottery);
+        buffer.extend_from_slice(&self.salt);
+        buffer.extend_from_slice(alphabet);
+
+        let alphabet_len = alphabet.len();
+        shuffle(alphabet, &buffer[..alphabet_len]);
+    }
+
+    /// Replaces confusable characters following the prefix with their
+    /// canonical alphabet characters.
+    pub(crate) fn normalize<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let canonical = |u: u8| {
+            self.normalization
+                .iter()
+                .find(|&&(from, _)| from == u)
+                .map(|&(_, to)| to)
+        };
+
+        if !input.bytes().any(|u| canonical(u).is_some()) {
+            return Cow::Borrowed(input);
+        }
+
+        let start = match &self.prefix {
+            Some(prefix) if input.starts_with(&**prefix) => prefix.len() + 1,
+            _ => 0,
+        };
+
+        let normalized = input
+            .char_indices()
+            .map(|(idx, c)| match c {
+                c if idx >= start && c.is_ascii() => canonical(c as u8).map_or(c, char::from),
+                c => c,
+            })
+            .collect();
+
+        Cow::Owned(normalized)
     }
 
     /// Encodes a hex string into a hashid.
+    ///
+    /// An empty hex string encodes to an empty hashid.
     pub fn encode_hex(&self, hex: &str) -> Result<String> {
         let values: Option<Vec<_>> = hex
             .as_bytes()
@@ -227,7 +552,8 @@ impl Harsh {
             .collect();
 
         match values {
-            Some(values) => Ok(self.encode(&values)),
+            Some(values) if values.is_empty() => Ok(String::new()),
+            Some(values) => Ok(self.try_encode(&values)?),
             None => Err(HarshError::Hex),
         }
     }
@@ -257,15 +583,30 @@ impl Default for Harsh {
     }
 }
 
+/// The largest single value whose hashid fits in `width` characters when
+/// hashed by `dialect` with an alphabet of `base` characters, if any value
+/// fits.
+pub(crate) fn max_value(dialect: Dialect, base: usize, width: usize) -> Option<u64> {
+    // A single value is hashed to a lottery character followed by its digits.
+    let digits = width.checked_sub(1).filter(|&digits| digits > 0)?;
+    Some((capacity::digit_limit(dialect, digits, base) - 1) as u64)
+}
+
 #[inline]
-fn create_nhash(values: &[u64]) -> u64 {
-    values
-        .iter()
-        .enumerate()
-        .fold(0, |a, (idx, value)| a + (value % (idx + 100) as u64))
+fn record<F: FnOnce() -> TraceStep>(trace: &mut Option<&mut Vec<TraceStep>>, step: F) {
+    if let Some(trace) = trace {
+        trace.push(step());
+    }
 }
 
-fn hash(mut value: u64, alphabet: &[u8]) -> String {
+#[inline]
+pub(crate) fn create_nhash(values: &[u64]) -> u64 {
+    values.iter().enumerate().fold(0u64, |a, (idx, value)| {
+        a.wrapping_add(value % (idx as u64).wrapping_add(100))
+    })
+}
+
+pub(crate) fn hash(mut value: u64, alphabet: &[u8]) -> Vec<u8> {
     let length = alphabet.len() as u64;
     let mut hash = Vec::new();
 
@@ -275,17 +616,42 @@ fn hash(mut value: u64, alphabet: &[u8]) -> String {
 
         if value == 0 {
             hash.reverse();
-            return String::from_utf8(hash).expect("omg fml");
+            return hash;
         }
     }
 }
 
-fn unhash(input: &[u8], alphabet: &[u8]) -> Option<u64> {
-    input.iter().enumerate().fold(Some(0), |a, (idx, &value)| {
-        let pos = alphabet.iter().position(|&item| item == value)? as u64;
-        let b = (alphabet.len() as u64).checked_pow((input.len() - idx - 1) as u32)?;
-        let c = pos.checked_mul(b)?;
-        a.map(|a| a + c)
+/// Computes a check character for `input` over an odd-sized alphabet.
+///
+/// Each step maps the running check `c` and the next character `x` to
+/// `2c + x (mod n)`. Because both 2 and 2 - 1 are invertible modulo an odd
+/// `n`, changing any single character or swapping two adjacent characters
+/// always changes the result.
+fn check_character(input: &[u8], alphabet: &[u8]) -> Option<u8> {
+    let n = alphabet.len();
+    let interim = input.iter().try_fold(0, |interim, &u| {
+        let idx = alphabet.iter().position(|&item| item == u)?;
+        Some((2 * interim + idx) % n)
+    })?;
+
+    Some(alphabet[(n - 2 * interim % n) % n])
+}
+
+/// Parses a single value, failing with `DecodeError::Value` on characters
+/// outside the alphabet and `DecodeError::Overflow` on values too large for
+/// a `u64`.
+pub(crate) fn unhash(input: &[u8], alphabet: &[u8]) -> Result<u64, DecodeError> {
+    input.iter().enumerate().try_fold(0u64, |a, (idx, &value)| {
+        let pos = alphabet
+            .iter()
+            .position(|&item| item == value)
+            .ok_or(DecodeError::Value)? as u64;
+        u32::try_from(input.len() - idx - 1)
+            .ok()
+            .and_then(|exp| (alphabet.len() as u64).checked_pow(exp))
+            .and_then(|b| pos.checked_mul(b))
+            .and_then(|c| a.checked_add(c))
+            .ok_or(DecodeError::Overflow)
     })
 }
 
@@ -610,7 +976,7 @@ mod tests {
     #[test]
     fn hash() {
         let result = super::hash(22, b"abcdefghijklmnopqrstuvwxyz");
-        assert_eq!("w", result);
+        assert_eq!(b"w", &result[..]);
     }
 
     #[test]
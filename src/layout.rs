@@ -0,0 +1,226 @@
+use crate::harsh::{DecodeError, EncodeError, Harsh, HarshError};
+use std::{error, fmt};
+
+/// Represents errors encountered when describing an `IdLayout`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// Error returned when the fields add up to more than 64 bits
+    Width(u32),
+
+    /// Error returned when a field is empty, unnamed or named twice
+    Field(String),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        static WIDTH_MESSAGE: &str = "The layout fields do not fit in 64 bits";
+        static FIELD_MESSAGE: &str = "The layout field is empty, unnamed or named twice";
+
+        match self {
+            LayoutError::Width(bits) => write!(f, "{} ({} bits)", WIDTH_MESSAGE, bits),
+            LayoutError::Field(name) => write!(f, "{} ({:?})", FIELD_MESSAGE, name),
+        }
+    }
+}
+
+impl error::Error for LayoutError {}
+
+/// A description of named bit fields packed into a single `u64`.
+///
+/// Fields are packed from the most significant end, in the order given, so
+/// a layout of `shard: 10, kind: 4, local: 50` stores the shard in the top
+/// ten bits. Packing the fields before encoding keeps ids short, and lets
+/// any one field be read back from a decoded id.
+///
+/// ```rust
+/// # use harsh::{Harsh, IdLayout};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let layout = IdLayout::new(&[("shard", 10), ("kind", 4), ("local", 50)])?;
+/// let harsh = Harsh::default();
+///
+/// let id = harsh.encode_packed(&layout, &[17, 2, 123_456])?;
+/// let packed = harsh.decode_one(&id)?;
+///
+/// assert_eq!(Some(17), layout.get(packed, "shard"));
+/// assert_eq!(vec![17, 2, 123_456], harsh.decode_packed(&layout, &id)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdLayout {
+    fields: Vec<(String, u32)>,
+}
+
+impl IdLayout {
+    /// Creates a layout from field names and widths in bits.
+    ///
+    /// Every field must have a unique, non-empty name and at least one bit,
+    /// and the widths may add up to at most 64 bits.
+    pub fn new<T: AsRef<str>>(fields: &[(T, u32)]) -> Result<IdLayout, LayoutError> {
+        let mut layout = Vec::with_capacity(fields.len());
+        let mut width = 0u32;
+
+        for (name, bits) in fields {
+            let name = name.as_ref();
+            if name.is_empty() || *bits == 0 || layout.iter().any(|(other, _)| other == name) {
+                return Err(LayoutError::Field(name.to_owned()));
+            }
+
+            width = width.saturating_add(*bits);
+            layout.push((name.to_owned(), *bits));
+        }
+
+        if width > 64 {
+            return Err(LayoutError::Width(width));
+        }
+
+        Ok(IdLayout { fields: layout })
+    }
+
+    /// The names and widths of the fields, most significant first.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.fields
+            .iter()
+            .map(|(name, bits)| (name.as_str(), *bits))
+    }
+
+    /// The total width of the fields in bits.
+    pub fn width(&self) -> u32 {
+        self.fields.iter().map(|&(_, bits)| bits).sum()
+    }
+
+    /// Packs one value per field into a single `u64`.
+    ///
+    /// Fails with `EncodeError::Arity` if there is not exactly one value per
+    /// field, or `EncodeError::Range` if a value does not fit its field.
+    pub fn pack(&self, values: &[u64]) -> Result<u64, EncodeError> {
+        if values.len() != self.fields.len() {
+            return Err(EncodeError::Arity(values.len()));
+        }
+
+        let mut packed = 0u64;
+        let mut shift = self.width();
+        for (index, (&value, &(_, bits))) in values.iter().zip(&self.fields).enumerate() {
+            if value > mask(bits) {
+                return Err(EncodeError::Range(index));
+            }
+            shift -= bits;
+            packed |= value.checked_shl(shift).unwrap_or(0);
+        }
+        Ok(packed)
+    }
+
+    /// Unpacks a `u64` into one value per field.
+    ///
+    /// Fails with `DecodeError::Layout` if bits above the width of the layout
+    /// are set.
+    pub fn unpack(&self, packed: u64) -> Result<Vec<u64>, DecodeError> {
+        let width = self.width();
+        if packed.checked_shr(width).unwrap_or(0) != 0 {
+            return Err(DecodeError::Layout);
+        }
+
+        let mut shift = width;
+        Ok(self
+            .fields
+            .iter()
+            .map(|&(_, bits)| {
+                shift -= bits;
+                packed.checked_shr(shift).unwrap_or(0) & mask(bits)
+            })
+            .collect())
+    }
+
+    /// Reads the field called `name` from a packed `u64`, if the layout has
+    /// such a field.
+    pub fn get(&self, packed: u64, name: &str) -> Option<u64> {
+        let mut shift = self.width();
+        for (field, bits) in &self.fields {
+            shift -= bits;
+            if field == name {
+                return Some(packed.checked_shr(shift).unwrap_or(0) & mask(*bits));
+            }
+        }
+        None
+    }
+}
+
+/// The largest value that fits in `bits` bits.
+pub(crate) fn mask(bits: u32) -> u64 {
+    u64::MAX.checked_shr(64 - bits).unwrap_or(0)
+}
+
+impl Harsh {
+    /// Packs one value per field of `layout` and encodes the result as a
+    /// single-value hashid.
+    pub fn encode_packed(&self, layout: &IdLayout, values: &[u64]) -> Result<String, EncodeError> {
+        self.try_encode(&[layout.pack(values)?])
+    }
+
+    /// Decodes a single-value hashid and unpacks it into one value per field
+    /// of `layout`.
+    pub fn decode_packed<T: AsRef<str>>(
+        &self,
+        layout: &IdLayout,
+        input: T,
+    ) -> Result<Vec<u64>, HarshError> {
+        Ok(layout.unpack(self.decode_one(input)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdLayout, LayoutError};
+    use crate::{DecodeError, EncodeError, Harsh, HarshError};
+
+    #[test]
+    fn fields_are_packed_most_significant_first() {
+        let layout = IdLayout::new(&[("shard", 10), ("kind", 4), ("local", 50)]).unwrap();
+        let packed = layout.pack(&[1, 2, 3]).unwrap();
+
+        assert_eq!((1 << 54) | (2 << 50) | 3, packed);
+        assert_eq!(Ok(vec![1, 2, 3]), layout.unpack(packed));
+        assert_eq!(Some(2), layout.get(packed, "kind"));
+        assert_eq!(None, layout.get(packed, "tenant"));
+    }
+
+    #[test]
+    fn full_width_fields() {
+        let layout = IdLayout::new(&[("id", 64)]).unwrap();
+        assert_eq!(Ok(u64::MAX), layout.pack(&[u64::MAX]));
+        assert_eq!(Ok(vec![u64::MAX]), layout.unpack(u64::MAX));
+    }
+
+    #[test]
+    fn ranges_are_checked() {
+        let layout = IdLayout::new(&[("shard", 10), ("local", 20)]).unwrap();
+
+        assert_eq!(Err(EncodeError::Range(0)), layout.pack(&[1024, 0]));
+        assert_eq!(Err(EncodeError::Arity(1)), layout.pack(&[1]));
+        assert_eq!(Err(DecodeError::Layout), layout.unpack(1 << 30));
+
+        let harsh = Harsh::default();
+        assert_eq!(
+            Err(HarshError::Decode(DecodeError::Layout)),
+            harsh.decode_packed(&layout, harsh.encode(&[1 << 30]))
+        );
+    }
+
+    #[test]
+    fn invalid_layouts() {
+        assert_eq!(
+            Err(LayoutError::Width(65)),
+            IdLayout::new(&[("a", 64), ("b", 1)])
+        );
+        assert_eq!(
+            Err(LayoutError::Field("a".to_owned())),
+            IdLayout::new(&[("a", 4), ("a", 4)])
+        );
+        assert_eq!(
+            Err(LayoutError::Field("b".to_owned())),
+            IdLayout::new(&[("a", 4), ("b", 0)])
+        );
+    }
+}
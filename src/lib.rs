@@ -59,11 +59,76 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! ## Rotating configurations
+//!
+//! A configuration built with a version prefixes its hashids with a tag
+//! character. [`VersionedHarsh`](./struct.VersionedHarsh.html) uses that tag
+//! to decode ids issued under older salts while encoding new ids with the
+//! current one.
+//!
+//! ```rust
+//! # use harsh::{Harsh, VersionedHarsh};
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let old = Harsh::builder().salt("old salt").version(0).build()?;
+//! let issued = old.encode(&[42]);
+//!
+//! let new = Harsh::builder().salt("new salt").version(1).build()?;
+//! let harsh = VersionedHarsh::new(new, vec![old])?;
+//!
+//! assert_eq!(harsh.decode(&issued)?, &[42]);
+//! # Ok(())
+//! # }
+//! ```
 
+mod alphabet;
+mod arity;
 mod builder;
+mod capacity;
+mod config;
+mod diagnostics;
+mod dialect;
+#[cfg(feature = "fingerprint")]
+mod fingerprint;
+mod generator;
 mod harsh;
+mod layout;
+#[cfg(feature = "keyed")]
+mod permuted;
+mod set;
+#[cfg(feature = "keyed")]
+mod signed;
+mod suggest;
+mod trace;
+mod value;
+mod versioned;
+
+#[cfg(feature = "derive")]
+pub use harsh_derive::HashIdValue;
+
+#[cfg(feature = "keyed")]
+pub use crate::{permuted::PermutedHarsh, signed::SignedHarsh};
+
+#[doc(hidden)]
+pub use crate::value::__private;
 
-pub use crate::harsh::Harsh;
+pub use crate::{
+    alphabet::Alphabet,
+    arity::HashIdValue,
+    builder::{BuildHarshError, HarshBuilder, HarshPreview},
+    capacity::Capacity,
+    config::HarshConfig,
+    diagnostics::{DecodeReport, DecodeStage, Segment},
+    dialect::Dialect,
+    generator::{
+        Clock, GeneratedId, Generator, GeneratorBuilder, GeneratorError, MockClock, SystemClock,
+    },
+    harsh::{DecodeError, EncodeError, Harsh, HarshError},
+    layout::{IdLayout, LayoutError},
+    trace::{Trace, TraceStep},
+    versioned::VersionedHarsh,
+};
 
 fn shuffle(values: &mut [u8], salt: &[u8]) {
     if salt.is_empty() {
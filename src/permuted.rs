@@ -0,0 +1,315 @@
+use crate::{
+    builder::BuildHarshError,
+    harsh::{DecodeError, EncodeError, Harsh, HarshError},
+    layout::mask,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+
+/// The number of Feistel rounds, which must be even so that the two halves
+/// end up the same width they started.
+const ROUNDS: u8 = 10;
+
+/// A `Harsh` that passes every value through a keyed permutation before
+/// encoding it.
+///
+/// Plain hashids leak structure: consecutive values produce ids that differ
+/// in only a few characters, and the salt can be recovered from a handful of
+/// samples. A `PermutedHarsh` first maps each value through a Feistel network
+/// keyed with HMAC-SHA256, so that consecutive values produce unrelated ids
+/// and the mapping cannot be learned without the key. The permutation is a
+/// bijection on values of the configured width, so every id decodes to
+/// exactly the values it was encoded from.
+///
+/// The key is independent of the salt and should be kept secret; the salt
+/// only shuffles the alphabet, as it does for a plain `Harsh`. Permuted
+/// values are spread across the whole width, so a narrower width gives
+/// shorter ids, at the cost of rejecting larger values.
+///
+/// Available with the `keyed` feature enabled.
+///
+/// ```rust
+/// # use harsh::{Harsh, PermutedHarsh};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let harsh = PermutedHarsh::with_width(Harsh::default(), "secret key", 40)?;
+///
+/// let first = harsh.encode(&[1]);
+/// let second = harsh.encode(&[2]);
+///
+/// assert_ne!(first[..4], second[..4]);
+/// assert_eq!(vec![2], harsh.decode(&second)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PermutedHarsh {
+    harsh: Harsh,
+    mac: Hmac<Sha256>,
+    width: u32,
+}
+
+impl PermutedHarsh {
+    /// Creates a `PermutedHarsh` permuting the full range of `u64` values.
+    ///
+    /// Fails with `BuildHarshError::Key` if `key` is empty.
+    pub fn new<K: AsRef<[u8]>>(harsh: Harsh, key: K) -> Result<Self, BuildHarshError> {
+        PermutedHarsh::with_width(harsh, key, 64)
+    }
+
+    /// Creates a `PermutedHarsh` permuting values below `2^bits`.
+    ///
+    /// Fails with `BuildHarshError::Key` if `key` is empty,
+    /// `BuildHarshError::Width` if `bits` is not between 2 and 64,
+    /// `BuildHarshError::WidthLimit` if the maximum value or exact length
+    /// configured on `harsh` rejects some values below `2^bits`, or
+    /// `BuildHarshError::Dialect` if `harsh` uses `Dialect::Legacy` and
+    /// `bits` exceeds 53, since that dialect rounds larger values.
+    pub fn with_width<K: AsRef<[u8]>>(
+        harsh: Harsh,
+        key: K,
+        bits: u32,
+    ) -> Result<Self, BuildHarshError> {
+        let key = key.as_ref();
+        if key.is_empty() {
+            return Err(BuildHarshError::Key);
+        }
+        if !(2..=64).contains(&bits) {
+            return Err(BuildHarshError::Width(bits));
+        }
+        if bits > harsh.dialect.exact_bits() {
+            return Err(BuildHarshError::Dialect);
+        }
+
+        // Any value of the width may come out of the permutation, so the
+        // largest must pass the inner limits; its hashid is a lottery
+        // character and its digits.
+        let max_value = mask(bits);
+        let min_hash_length = 1 + harsh.dialect.digits(max_value, harsh.alphabet.len());
+        if harsh.limits.value.is_some_and(|max| max < max_value)
+            || (harsh.exact_length && harsh.hash_length < min_hash_length)
+        {
+            return Err(BuildHarshError::WidthLimit);
+        }
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|_| BuildHarshError::Key)?;
+        mac.update(b"harsh-feistel");
+        mac.update(&bits.to_be_bytes());
+
+        Ok(PermutedHarsh {
+            harsh,
+            mac,
+            width: bits,
+        })
+    }
+
+    /// The configuration used to encode permuted values.
+    pub fn inner(&self) -> &Harsh {
+        &self.harsh
+    }
+
+    /// The width of the permuted values in bits.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Permutes a single value, or returns `None` if it does not fit the
+    /// width.
+    pub fn permute(&self, value: u64) -> Option<u64> {
+        if value > mask(self.width) {
+            return None;
+        }
+
+        let (mut left_bits, mut right_bits) = (self.width / 2, self.width - self.width / 2);
+        let mut left = value >> right_bits;
+        let mut right = value & mask(right_bits);
+
+        for round in 0..ROUNDS {
+            let next = left ^ self.round(round, left_bits, right);
+            left = right;
+            right = next;
+            std::mem::swap(&mut left_bits, &mut right_bits);
+        }
+
+        Some((left << right_bits) | right)
+    }
+
+    /// Reverses `permute`, or returns `None` if `value` does not fit the
+    /// width.
+    pub fn unpermute(&self, value: u64) -> Option<u64> {
+        if value > mask(self.width) {
+            return None;
+        }
+
+        let (mut left_bits, mut right_bits) = (self.width / 2, self.width - self.width / 2);
+        let mut left = value >> right_bits;
+        let mut right = value & mask(right_bits);
+
+        for round in (0..ROUNDS).rev() {
+            let previous = right ^ self.round(round, right_bits, left);
+            right = left;
+            left = previous;
+            std::mem::swap(&mut left_bits, &mut right_bits);
+        }
+
+        Some((left << right_bits) | right)
+    }
+
+    /// Permutes and encodes a slice of `u64` values.
+    ///
+    /// Returns an empty string if the values cannot be encoded. Use
+    /// `try_encode` to find out why.
+    pub fn encode(&self, values: &[u64]) -> String {
+        self.try_encode(values).unwrap_or_default()
+    }
+
+    /// Permutes and encodes a slice of `u64` values.
+    ///
+    /// Fails with `EncodeError::Range` if a value does not fit the width, and
+    /// otherwise in the same cases as `Harsh::try_encode`.
+    pub fn try_encode(&self, values: &[u64]) -> Result<String, EncodeError> {
+        let permuted = values
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| self.permute(value).ok_or(EncodeError::Range(index)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.harsh.try_encode(&permuted)
+    }
+
+    /// Decodes a hashid and reverses the permutation of each value.
+    ///
+    /// Fails with `DecodeError::Range` if a decoded value does not fit the
+    /// width, which can only happen for ids not produced by this instance.
+    pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<Vec<u64>, HarshError> {
+        self.harsh
+            .decode(input)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                self.unpermute(value)
+                    .ok_or(HarshError::Decode(DecodeError::Range(index)))
+            })
+            .collect()
+    }
+
+    /// The round function: a keyed hash of the round and the right half,
+    /// truncated to `bits` bits.
+    fn round(&self, round: u8, bits: u32, half: u64) -> u64 {
+        let mut mac = self.mac.clone();
+        mac.update(&[round]);
+        mac.update(&half.to_be_bytes());
+
+        let tag = mac.finalize().into_bytes();
+        let mut prefix = [0; 8];
+        prefix.copy_from_slice(&tag[..8]);
+        u64::from_be_bytes(prefix) & mask(bits)
+    }
+}
+
+impl fmt::Debug for PermutedHarsh {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PermutedHarsh")
+            .field("harsh", &self.harsh)
+            .field("width", &self.width)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PermutedHarsh;
+    use crate::{BuildHarshError, DecodeError, Dialect, EncodeError, Harsh, HarshError};
+    use std::collections::HashSet;
+
+    #[test]
+    fn small_widths_are_bijections() {
+        for bits in 2..=12 {
+            let harsh = PermutedHarsh::with_width(Harsh::default(), "key", bits).unwrap();
+            let permuted: HashSet<_> = (0..1u64 << bits)
+                .map(|value| {
+                    let permuted = harsh.permute(value).unwrap();
+                    assert_eq!(Some(value), harsh.unpermute(permuted));
+                    permuted
+                })
+                .collect();
+
+            assert_eq!(1 << bits, permuted.len());
+            assert!(permuted.iter().all(|&value| value < 1 << bits));
+        }
+    }
+
+    #[test]
+    fn round_trips_full_width() {
+        let harsh = PermutedHarsh::new(Harsh::default(), "key").unwrap();
+        let values = [0, 1, 2, u64::MAX - 1, u64::MAX];
+
+        let id = harsh.encode(&values);
+        assert_eq!(values.to_vec(), harsh.decode(&id).unwrap());
+        assert_ne!(Harsh::default().encode(&values), id);
+    }
+
+    #[test]
+    fn keys_give_different_permutations() {
+        let first = PermutedHarsh::new(Harsh::default(), "first").unwrap();
+        let second = PermutedHarsh::new(Harsh::default(), "second").unwrap();
+
+        assert_ne!(first.permute(1), second.permute(1));
+        assert_ne!(first.encode(&[1]), second.encode(&[1]));
+    }
+
+    #[test]
+    fn width_is_enforced() {
+        let harsh = PermutedHarsh::with_width(Harsh::default(), "key", 16).unwrap();
+
+        assert_eq!(Err(EncodeError::Range(1)), harsh.try_encode(&[1, 1 << 16]));
+        assert_eq!(
+            Err(HarshError::Decode(DecodeError::Range(0))),
+            harsh.decode(Harsh::default().encode(&[1 << 16]))
+        );
+
+        assert!(matches!(
+            PermutedHarsh::with_width(Harsh::default(), "key", 65),
+            Err(BuildHarshError::Width(65))
+        ));
+        assert!(matches!(
+            PermutedHarsh::new(Harsh::default(), ""),
+            Err(BuildHarshError::Key)
+        ));
+    }
+
+    #[test]
+    fn widths_must_fit_limits() {
+        let limited = || Harsh::builder().max_value(1000).build().unwrap();
+        assert!(matches!(
+            PermutedHarsh::with_width(limited(), "key", 16),
+            Err(BuildHarshError::WidthLimit)
+        ));
+        let harsh = PermutedHarsh::with_width(limited(), "key", 9).unwrap();
+        assert_eq!(Ok(vec![511]), harsh.decode(harsh.encode(&[511])));
+
+        let exact = || Harsh::builder().exact_length(4).build().unwrap();
+        assert!(matches!(
+            PermutedHarsh::with_width(exact(), "key", 32),
+            Err(BuildHarshError::WidthLimit)
+        ));
+        let harsh = PermutedHarsh::with_width(exact(), "key", 16).unwrap();
+        let id = harsh.try_encode(&[u16::MAX as u64]).unwrap();
+        assert_eq!(4, id.len());
+        assert_eq!(Ok(vec![u16::MAX as u64]), harsh.decode(id));
+    }
+
+    #[test]
+    fn legacy_widths_are_exact() {
+        let legacy = || Harsh::builder().dialect(Dialect::Legacy).build().unwrap();
+        assert!(matches!(
+            PermutedHarsh::new(legacy(), "key"),
+            Err(BuildHarshError::Dialect)
+        ));
+
+        let harsh = PermutedHarsh::with_width(legacy(), "key", 53).unwrap();
+        let value = (1 << 53) - 1;
+        assert_eq!(Ok(vec![value]), harsh.decode(harsh.encode(&[value])));
+    }
+}
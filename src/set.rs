@@ -0,0 +1,99 @@
+use crate::harsh::{DecodeError, EncodeError, Harsh, HarshError};
+
+impl Harsh {
+    /// Encodes a set of `u64` values into a single hashid.
+    ///
+    /// The values are sorted and deduplicated, and each is then encoded as
+    /// its distance from the previous one, so that sets of nearby values
+    /// produce much shorter hashids than `encode`. Fails like `try_encode`.
+    pub fn encode_set(&self, values: &[u64]) -> Result<String, EncodeError> {
+        let mut values = values.to_vec();
+        values.sort_unstable();
+        values.dedup();
+
+        let mut previous = None;
+        let deltas: Vec<_> = values
+            .iter()
+            .map(|&value| {
+                // Successive values differ by at least one, which need not
+                // be encoded.
+                let delta = match previous {
+                    None => value,
+                    Some(previous) => value - previous - 1,
+                };
+                previous = Some(value);
+                delta
+            })
+            .collect();
+
+        self.try_encode(&deltas)
+    }
+
+    /// Decodes a hashid produced by `encode_set` into its values, in
+    /// ascending order.
+    ///
+    /// Fails with `DecodeError::Overflow` if the encoded distances describe
+    /// values too large for a `u64`, which `encode_set` never produces.
+    pub fn decode_set<T: AsRef<str>>(&self, input: T) -> Result<Vec<u64>, HarshError> {
+        let deltas = self.decode(input)?;
+
+        let mut previous: Option<u64> = None;
+        deltas
+            .iter()
+            .map(|&delta| {
+                let value = match previous {
+                    None => Some(delta),
+                    Some(previous) => previous
+                        .checked_add(delta)
+                        .and_then(|value| value.checked_add(1)),
+                };
+                previous = value;
+                value.ok_or(HarshError::Decode(DecodeError::Overflow))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DecodeError, EncodeError, Harsh, HarshError};
+
+    #[test]
+    fn sets_round_trip() {
+        let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+        let id = harsh.encode_set(&[1005, 1001, 1002, 1001, 0]).unwrap();
+
+        assert_eq!(Ok(vec![0, 1001, 1002, 1005]), harsh.decode_set(&id));
+        assert_eq!(Err(EncodeError::Empty), harsh.encode_set(&[]));
+    }
+
+    #[test]
+    fn clustered_sets_are_shorter() {
+        let harsh = Harsh::default();
+        let values: Vec<_> = (0..200).map(|n| 9_000_000_000 + n * 3).collect();
+
+        let set = harsh.encode_set(&values).unwrap();
+        assert!(set.len() * 3 < harsh.encode(&values).len());
+        assert_eq!(Ok(values), harsh.decode_set(&set));
+    }
+
+    #[test]
+    fn large_values_round_trip() {
+        let harsh = Harsh::default();
+        let values = vec![0, u64::MAX - 1, u64::MAX];
+
+        let id = harsh.encode_set(&values).unwrap();
+        assert_eq!(Ok(values), harsh.decode_set(&id));
+    }
+
+    #[test]
+    fn overflowing_sets_are_rejected() {
+        let harsh = Harsh::default();
+        let id = harsh.encode(&[u64::MAX, 0]);
+
+        assert_eq!(
+            Err(HarshError::Decode(DecodeError::Overflow)),
+            harsh.decode_set(&id)
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{
+    builder::BuildHarshError,
+    capacity::digits,
+    harsh::{DecodeError, EncodeError, Harsh, HarshError},
+    layout::mask,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+
+/// The default tag length in bytes.
+const DEFAULT_TAG_LENGTH: usize = 8;
+
+/// A `Harsh` that appends a keyed signature to every hashid.
+///
+/// The tag is an HMAC-SHA256 of the values, truncated to the configured
+/// length and encoded as one extra value at the end of the hashid. Decoding
+/// recomputes the tag and compares it in constant time, so ids cannot be
+/// forged or altered without the secret. The secret is independent of the
+/// salt, which only shuffles the alphabet as it does for a plain `Harsh`.
+///
+/// An optional context, such as the kind of resource an id refers to, can be
+/// bound into the tag so that an id issued for one purpose is rejected when
+/// presented for another. The context is not stored in the hashid and must be
+/// supplied again when decoding.
+///
+/// Every byte of tag lengthens the id, and every byte removed makes a forged
+/// id 256 times easier to guess. The default of 8 bytes suits links that may
+/// be attacked online; shorter tags should be paired with rate limiting.
+///
+/// Available with the `keyed` feature enabled.
+///
+/// ```rust
+/// # use harsh::{Harsh, SignedHarsh};
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let harsh = SignedHarsh::new(Harsh::default(), "signing secret")?;
+///
+/// let id = harsh.encode_with_context(&[42], b"invoice")?;
+///
+/// assert_eq!(vec![42], harsh.decode_with_context(&id, b"invoice")?);
+/// assert!(harsh.decode_with_context(&id, b"unsubscribe").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SignedHarsh {
+    harsh: Harsh,
+    mac: Hmac<Sha256>,
+    tag_length: usize,
+}
+
+impl SignedHarsh {
+    /// Creates a `SignedHarsh` with 8-byte tags.
+    ///
+    /// Fails with `BuildHarshError::Key` if `secret` is empty.
+    pub fn new<K: AsRef<[u8]>>(harsh: Harsh, secret: K) -> Result<Self, BuildHarshError> {
+        SignedHarsh::with_tag_length(harsh, secret, DEFAULT_TAG_LENGTH)
+    }
+
+    /// Creates a `SignedHarsh` with tags of `tag_length` bytes.
+    ///
+    /// Fails with `BuildHarshError::Key` if `secret` is empty,
+    /// `BuildHarshError::TagLength` if `tag_length` is not between 1 and 8, or
+    /// `BuildHarshError::TagLimit` if the tag would not fit the maximum value,
+    /// maximum number of values or exact length configured on `harsh`, or
+    /// `BuildHarshError::Dialect` if `harsh` uses `Dialect::Legacy` and
+    /// `tag_length` exceeds 6, since that dialect rounds tags above `2^53`.
+    ///
+    /// The tag counts as one value towards those limits, so a maximum of `n`
+    /// values leaves room for `n - 1` signed values.
+    pub fn with_tag_length<K: AsRef<[u8]>>(
+        harsh: Harsh,
+        secret: K,
+        tag_length: usize,
+    ) -> Result<Self, BuildHarshError> {
+        let secret = secret.as_ref();
+        if secret.is_empty() {
+            return Err(BuildHarshError::Key);
+        }
+        if !(1..=8).contains(&tag_length) {
+            return Err(BuildHarshError::TagLength(tag_length));
+        }
+
+        if 8 * tag_length as u32 > harsh.dialect.exact_bits() {
+            return Err(BuildHarshError::Dialect);
+        }
+
+        // The smallest signed hashid is a lottery character, one digit, a
+        // separator and the tag.
+        let max_tag = mask(8 * tag_length as u32);
+        let min_hash_length = 3 + digits(max_tag, harsh.alphabet.len());
+        if harsh.limits.value.is_some_and(|max| max < max_tag)
+            || harsh.limits.values.is_some_and(|max| max < 2)
+            || (harsh.exact_length && harsh.hash_length < min_hash_length)
+        {
+            return Err(BuildHarshError::TagLimit);
+        }
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).map_err(|_| BuildHarshError::Key)?;
+        mac.update(b"harsh-signed");
+        mac.update(&[tag_length as u8]);
+
+        Ok(SignedHarsh {
+            harsh,
+            mac,
+            tag_length,
+        })
+    }
+
+    /// The configuration used to encode signed values.
+    pub fn inner(&self) -> &Harsh {
+        &self.harsh
+    }
+
+    /// The length of the tags in bytes.
+    pub fn tag_length(&self) -> usize {
+        self.tag_length
+    }
+
+    /// Signs and encodes a slice of `u64` values.
+    ///
+    /// Returns an empty string if the values cannot be encoded. Use
+    /// `try_encode` to find out why.
+    pub fn encode(&self, values: &[u64]) -> String {
+        self.try_encode(values).unwrap_or_default()
+    }
+
+    /// Signs and encodes a slice of `u64` values, failing in the same cases
+    /// as `Harsh::try_encode`.
+    pub fn try_encode(&self, values: &[u64]) -> Result<String, EncodeError> {
+        self.encode_with_context(values, &[])
+    }
+
+    /// Signs and encodes a slice of `u64` values, binding `context` into the
+    /// tag.
+    pub fn encode_with_context(
+        &self,
+        values: &[u64],
+        context: &[u8],
+    ) -> Result<String, EncodeError> {
+        if values.is_empty() {
+            return Err(EncodeError::Empty);
+        }
+
+        let tag = self.mac(values, context).finalize().into_bytes();
+        let mut bytes = [0; 8];
+        bytes[(8 - self.tag_length)..].copy_from_slice(&tag[..self.tag_length]);
+
+        let mut signed = values.to_vec();
+        signed.push(u64::from_be_bytes(bytes));
+        self.harsh.try_encode(&signed)
+    }
+
+    /// Decodes a hashid and verifies its tag.
+    ///
+    /// Fails with `DecodeError::Signature` if the tag is missing or does not
+    /// match the values, and otherwise in the same cases as `Harsh::decode`.
+    pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<Vec<u64>, HarshError> {
+        self.decode_with_context(input, &[])
+    }
+
+    /// Decodes a hashid and verifies its tag against `context`.
+    pub fn decode_with_context<T: AsRef<str>>(
+        &self,
+        input: T,
+        context: &[u8],
+    ) -> Result<Vec<u64>, HarshError> {
+        let mut values = self.harsh.decode(input)?;
+        let tag = match values.pop() {
+            Some(tag) if !values.is_empty() && tag <= mask(8 * self.tag_length as u32) => tag,
+            _ => return Err(HarshError::Decode(DecodeError::Signature)),
+        };
+
+        self.mac(&values, context)
+            .verify_truncated_left(&tag.to_be_bytes()[(8 - self.tag_length)..])
+            .map_err(|_| HarshError::Decode(DecodeError::Signature))?;
+
+        Ok(values)
+    }
+
+    /// The keyed MAC over the context and values, before finalization.
+    ///
+    /// The context is prefixed with its length so that no context and value
+    /// sequence can be mistaken for another.
+    fn mac(&self, values: &[u64], context: &[u8]) -> Hmac<Sha256> {
+        let mut mac = self.mac.clone();
+        mac.update(&(context.len() as u64).to_be_bytes());
+        mac.update(context);
+        for value in values {
+            mac.update(&value.to_be_bytes());
+        }
+        mac
+    }
+}
+
+impl fmt::Debug for SignedHarsh {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignedHarsh")
+            .field("harsh", &self.harsh)
+            .field("tag_length", &self.tag_length)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignedHarsh;
+    use crate::{BuildHarshError, DecodeError, Dialect, Harsh, HarshError};
+
+    const SIGNATURE: Result<Vec<u64>, HarshError> = Err(HarshError::Decode(DecodeError::Signature));
+
+    #[test]
+    fn round_trips() {
+        for tag_length in 1..=8 {
+            let harsh =
+                SignedHarsh::with_tag_length(Harsh::default(), "secret", tag_length).unwrap();
+            let id = harsh.encode(&[1, 2, 3]);
+
+            assert_eq!(Ok(vec![1, 2, 3]), harsh.decode(&id));
+            assert_eq!(4, harsh.inner().decode(&id).unwrap().len());
+        }
+    }
+
+    #[test]
+    fn rejects_altered_values() {
+        let harsh = SignedHarsh::new(Harsh::default(), "secret").unwrap();
+        let mut values = harsh.inner().decode(harsh.encode(&[7, 8])).unwrap();
+
+        values[0] += 1;
+        assert_eq!(SIGNATURE, harsh.decode(harsh.inner().encode(&values)));
+        assert_eq!(SIGNATURE, harsh.decode(harsh.inner().encode(&values[1..])));
+        assert_eq!(SIGNATURE, harsh.decode(Harsh::default().encode(&[7])));
+    }
+
+    #[test]
+    fn rejects_other_secrets_and_contexts() {
+        let harsh = SignedHarsh::new(Harsh::default(), "secret").unwrap();
+        let other = SignedHarsh::new(Harsh::default(), "other").unwrap();
+
+        let id = harsh.encode_with_context(&[5], b"invoice").unwrap();
+        assert_eq!(Ok(vec![5]), harsh.decode_with_context(&id, b"invoice"));
+        assert_eq!(SIGNATURE, harsh.decode_with_context(&id, b"unsubscribe"));
+        assert_eq!(SIGNATURE, harsh.decode(&id));
+        assert_eq!(SIGNATURE, other.decode_with_context(&id, b"invoice"));
+    }
+
+    #[test]
+    fn rejects_oversized_tags() {
+        let harsh = SignedHarsh::with_tag_length(Harsh::default(), "secret", 2).unwrap();
+        let mut values = harsh.inner().decode(harsh.encode(&[9])).unwrap();
+
+        values[1] |= 1 << 16;
+        assert_eq!(SIGNATURE, harsh.decode(harsh.inner().encode(&values)));
+    }
+
+    #[test]
+    fn tags_must_fit_limits() {
+        let limited = || Harsh::builder().max_value(1000).build().unwrap();
+        assert!(matches!(
+            SignedHarsh::new(limited(), "secret"),
+            Err(BuildHarshError::TagLimit)
+        ));
+        let harsh = SignedHarsh::with_tag_length(limited(), "secret", 1).unwrap();
+        assert_eq!(Ok(vec![1000]), harsh.decode(harsh.encode(&[1000])));
+
+        let exact = || Harsh::builder().exact_length(8).build().unwrap();
+        assert!(matches!(
+            SignedHarsh::new(exact(), "secret"),
+            Err(BuildHarshError::TagLimit)
+        ));
+        let harsh = SignedHarsh::with_tag_length(exact(), "secret", 1).unwrap();
+        let id = harsh.try_encode(&[5]).unwrap();
+        assert_eq!(8, id.len());
+        assert_eq!(Ok(vec![5]), harsh.decode(&id));
+
+        let single = Harsh::builder().max_values(1).build().unwrap();
+        assert!(matches!(
+            SignedHarsh::new(single, "secret"),
+            Err(BuildHarshError::TagLimit)
+        ));
+    }
+
+    #[test]
+    fn invalid_configurations() {
+        assert!(matches!(
+            SignedHarsh::new(Harsh::default(), ""),
+            Err(BuildHarshError::Key)
+        ));
+        assert!(matches!(
+            SignedHarsh::with_tag_length(Harsh::default(), "secret", 9),
+            Err(BuildHarshError::TagLength(9))
+        ));
+
+        let legacy = || Harsh::builder().dialect(Dialect::Legacy).build().unwrap();
+        assert!(matches!(
+            SignedHarsh::new(legacy(), "secret"),
+            Err(BuildHarshError::Dialect)
+        ));
+        let harsh = SignedHarsh::with_tag_length(legacy(), "secret", 6).unwrap();
+        assert_eq!(Ok(vec![42]), harsh.decode(harsh.encode(&[42])));
+    }
+}
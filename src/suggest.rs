@@ -0,0 +1,220 @@
+use crate::harsh::Harsh;
+use std::{collections::HashMap, str};
+
+/// Pairs of characters commonly mistaken for one another, with the relative
+/// likelihood of the mistake.
+const CONFUSABLES: &[(u8, u8, f64)] = &[
+    (b'0', b'O', 1.0),
+    (b'0', b'o', 0.9),
+    (b'O', b'o', 0.8),
+    (b'0', b'D', 0.6),
+    (b'1', b'l', 1.0),
+    (b'1', b'I', 1.0),
+    (b'l', b'I', 1.0),
+    (b'1', b'i', 0.7),
+    (b'1', b'7', 0.5),
+    (b'2', b'Z', 0.7),
+    (b'2', b'z', 0.6),
+    (b'5', b'S', 0.8),
+    (b'5', b's', 0.7),
+    (b'6', b'G', 0.6),
+    (b'6', b'b', 0.5),
+    (b'8', b'B', 0.8),
+    (b'9', b'g', 0.6),
+    (b'9', b'q', 0.6),
+    (b'u', b'v', 0.6),
+    (b'U', b'V', 0.6),
+    (b'n', b'h', 0.5),
+    (b'm', b'n', 0.5),
+    (b'c', b'e', 0.5),
+];
+
+const CASE_WEIGHT: f64 = 0.6;
+const TRANSPOSITION_WEIGHT: f64 = 0.5;
+const DELETION_WEIGHT: f64 = 0.3;
+const INSERTION_WEIGHT: f64 = 0.2;
+const SUBSTITUTION_WEIGHT: f64 = 0.2;
+
+/// The longest input for which suggestions are generated, whatever the
+/// configured input limit, since the work grows with the square of its length.
+const MAX_SUGGEST_LENGTH: usize = 128;
+
+impl Harsh {
+    /// Suggests valid hashids within one edit of `input`.
+    ///
+    /// Candidates are produced by substituting, transposing, inserting or
+    /// deleting a single character, and are kept only if they decode
+    /// successfully. The result is ordered from most to least likely, with
+    /// substitutions of easily-confused characters (such as `0` and `O`)
+    /// ranked first. If `input` is itself valid, its canonical form is the
+    /// only suggestion. Suggestions are always canonical, with any declared
+    /// confusable characters replaced by their alphabet characters.
+    ///
+    /// Input longer than the configured maximum input length, or than 128
+    /// bytes, yields no suggestions.
+    pub fn suggest(&self, input: &str) -> Vec<String> {
+        let max_length = self
+            .limits
+            .input_length
+            .map_or(MAX_SUGGEST_LENGTH, |max| max.min(MAX_SUGGEST_LENGTH));
+        if input.len() > max_length {
+            return Vec::new();
+        }
+
+        if self.decode(input).is_ok() {
+            return vec![self.normalize(input).into_owned()];
+        }
+
+        let input = input.as_bytes();
+        let characters: Vec<u8> = self
+            .alphabet
+            .iter()
+            .chain(self.separators.iter())
+            .chain(self.guards.iter())
+            .cloned()
+            .collect();
+
+        // Candidates are decoded as they are generated, so that only the few
+        // valid ones are ever stored.
+        let mut suggestions = HashMap::new();
+        let mut consider = |candidate: &[u8], weight: f64| {
+            let candidate = match str::from_utf8(candidate) {
+                Ok(candidate) => self.normalize(candidate),
+                Err(_) => return,
+            };
+
+            match suggestions.get_mut(&*candidate) {
+                Some(score) if *score < weight => *score = weight,
+                Some(_) => {}
+                None if self.decode(&candidate).is_ok() => {
+                    suggestions.insert(candidate.into_owned(), weight);
+                }
+                None => {}
+            }
+        };
+
+        let mut candidate = input.to_vec();
+        for idx in 0..input.len() {
+            for &u in &characters {
+                if u != input[idx] {
+                    candidate[idx] = u;
+                    consider(&candidate, substitution_weight(input[idx], u));
+                }
+            }
+            candidate[idx] = input[idx];
+
+            if idx + 1 < input.len() && input[idx] != input[idx + 1] {
+                candidate.swap(idx, idx + 1);
+                consider(&candidate, TRANSPOSITION_WEIGHT);
+                candidate.swap(idx, idx + 1);
+            }
+        }
+
+        for (idx, &u) in input.iter().enumerate() {
+            candidate.remove(idx);
+            consider(&candidate, DELETION_WEIGHT);
+            candidate.insert(idx, u);
+        }
+
+        for idx in 0..=input.len() {
+            candidate.insert(idx, 0);
+            for &u in &characters {
+                candidate[idx] = u;
+                consider(&candidate, INSERTION_WEIGHT);
+            }
+            candidate.remove(idx);
+        }
+
+        let mut suggestions: Vec<_> = suggestions.into_iter().collect();
+        suggestions.sort_by(|(a, a_score), (b, b_score)| {
+            b_score.total_cmp(a_score).then_with(|| a.cmp(b))
+        });
+        suggestions
+            .into_iter()
+            .map(|(candidate, _)| candidate)
+            .collect()
+    }
+}
+
+fn substitution_weight(typed: u8, intended: u8) -> f64 {
+    let confusable = CONFUSABLES
+        .iter()
+        .find(|&&(a, b, _)| (a, b) == (typed, intended) || (b, a) == (typed, intended))
+        .map(|&(_, _, weight)| weight);
+
+    match confusable {
+        Some(weight) => weight,
+        None if typed.eq_ignore_ascii_case(&intended) => CASE_WEIGHT,
+        None => SUBSTITUTION_WEIGHT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Harsh;
+
+    #[test]
+    fn valid_input_is_its_own_suggestion() {
+        let harsh = Harsh::default();
+        assert_eq!(vec!["o2fXhV".to_owned()], harsh.suggest("o2fXhV"));
+    }
+
+    #[test]
+    fn suggestions_decode() {
+        let harsh = Harsh::default();
+        let suggestions = harsh.suggest("o2fXhW");
+
+        assert!(!suggestions.is_empty());
+        for suggestion in &suggestions {
+            assert!(harsh.decode(suggestion).is_ok(), "{}", suggestion);
+        }
+    }
+
+    #[test]
+    fn confusable_substitutions_rank_first() {
+        let harsh = Harsh::builder().length(8).build().unwrap();
+        let id = harsh.encode(&[1, 2, 3]);
+        let idx = id.find('o').expect("fixture contains an `o`");
+
+        let mut typo = id.clone();
+        typo.replace_range(idx..=idx, "0");
+
+        assert_eq!(Some(&id), harsh.suggest(&typo).first());
+    }
+
+    #[test]
+    fn suggestions_are_canonical() {
+        let harsh = Harsh::builder()
+            .alphabet("abcdefghijkmnpqrstuvwxyzABCDEFGHJKLMNPQRTUVWXYZ0123456789")
+            .confusables(vec!["0Oo", "1lI"])
+            .build()
+            .unwrap();
+        assert_eq!("071", harsh.encode(&[130]));
+
+        assert_eq!(vec!["071".to_owned()], harsh.suggest("O7l"));
+
+        let suggestions = harsh.suggest("O7lk");
+        assert!(suggestions.contains(&"071".to_owned()));
+        for suggestion in &suggestions {
+            assert!(
+                !suggestion.contains(|c| "OolI".contains(c)),
+                "{}",
+                suggestion
+            );
+        }
+    }
+
+    #[test]
+    fn long_input_is_ignored() {
+        let harsh = Harsh::builder().max_input_length(20).build().unwrap();
+        assert!(harsh.suggest(&"o".repeat(21)).is_empty());
+        assert!(Harsh::default().suggest(&"o".repeat(800)).is_empty());
+    }
+
+    #[test]
+    fn transpositions_are_found() {
+        let harsh = Harsh::default();
+        let suggestions = harsh.suggest("o2fXVh");
+        assert!(suggestions.contains(&"o2fXhV".to_owned()));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::harsh::{EncodeError, Harsh};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A record of every intermediate state produced while encoding a hashid.
+///
+/// Traces are meant to be compared against traces from other Hashids
+/// implementations: the `Display` output has one step per line, and with the
+/// `serde` feature enabled a trace serializes to a list of tagged steps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trace {
+    /// The values that were encoded.
+    pub values: Vec<u64>,
+    /// The steps taken, in order.
+    pub steps: Vec<TraceStep>,
+    /// The resulting hashid.
+    pub hashid: String,
+}
+
+/// A single step of the encoding algorithm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "step", rename_all = "snake_case"))]
+pub enum TraceStep {
+    /// The hash of all values that selects the lottery and guards.
+    Nhash { value: u64 },
+    /// The lottery character, and its index in the alphabet.
+    Lottery { index: usize, character: char },
+    /// The alphabet after being shuffled for the next value.
+    Shuffle { alphabet: String },
+    /// A value and the characters it was hashed to.
+    Hash { value: u64, output: String },
+    /// The separator following a value, and its index in the separators.
+    Separator { index: usize, character: char },
+    /// The guard inserted at the front, and its index in the guards.
+    LeftGuard { index: usize, character: char },
+    /// The guard appended at the back, and its index in the guards.
+    RightGuard { index: usize, character: char },
+    /// A round of padding: the reshuffled alphabet and the padded hashid.
+    Padding { alphabet: String, output: String },
+    /// The version tag inserted at the front.
+    Version { character: char },
+    /// The check character appended at the back.
+    Check { character: char },
+    /// The prefix inserted at the front.
+    Prefix { prefix: String },
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraceStep::Nhash { value } => write!(f, "nhash {}", value),
+            TraceStep::Lottery { index, character } => write!(f, "lottery {} {}", index, character),
+            TraceStep::Shuffle { alphabet } => write!(f, "shuffle {}", alphabet),
+            TraceStep::Hash { value, output } => write!(f, "hash {} {}", value, output),
+            TraceStep::Separator { index, character } => {
+                write!(f, "separator {} {}", index, character)
+            }
+            TraceStep::LeftGuard { index, character } => {
+                write!(f, "left_guard {} {}", index, character)
+            }
+            TraceStep::RightGuard { index, character } => {
+                write!(f, "right_guard {} {}", index, character)
+            }
+            TraceStep::Padding { alphabet, output } => write!(f, "padding {} {}", alphabet, output),
+            TraceStep::Version { character } => write!(f, "version {}", character),
+            TraceStep::Check { character } => write!(f, "check {}", character),
+            TraceStep::Prefix { prefix } => write!(f, "prefix {}", prefix),
+        }
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values: Vec<_> = self.values.iter().map(u64::to_string).collect();
+        writeln!(f, "values {}", values.join(","))?;
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        write!(f, "hashid {}", self.hashid)
+    }
+}
+
+impl Harsh {
+    /// Encodes a slice of `u64` values, recording each intermediate state.
+    ///
+    /// The resulting hashid is the same as that returned by `try_encode`,
+    /// which fails in the same cases.
+    pub fn encode_traced(&self, values: &[u64]) -> Result<Trace, EncodeError> {
+        let mut steps = Vec::new();
+        let hashid = self.encode_with(values, &mut Some(&mut steps))?;
+
+        Ok(Trace {
+            values: values.to_vec(),
+            steps,
+            hashid,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TraceStep;
+    use crate::Harsh;
+
+    #[test]
+    fn trace_matches_encode() {
+        let harsh = Harsh::builder()
+            .salt("this is my salt")
+            .length(20)
+            .build()
+            .unwrap();
+        let trace = harsh.encode_traced(&[1, 2, 3]).unwrap();
+
+        assert_eq!(harsh.encode(&[1, 2, 3]), trace.hashid);
+        assert_eq!(Some(&TraceStep::Nhash { value: 6 }), trace.steps.first());
+        assert_eq!(
+            3,
+            trace
+                .steps
+                .iter()
+                .filter(|step| matches!(step, TraceStep::Hash { .. }))
+                .count()
+        );
+        assert!(trace
+            .steps
+            .iter()
+            .any(|step| matches!(step, TraceStep::Padding { .. })));
+    }
+
+    #[test]
+    fn trace_displays_one_step_per_line() {
+        let harsh = Harsh::default();
+        let trace = harsh.encode_traced(&[1, 2, 3]).unwrap();
+
+        let lines: Vec<_> = trace.to_string().lines().map(String::from).collect();
+
+        assert_eq!(trace.steps.len() + 2, lines.len());
+        assert_eq!("values 1,2,3", lines[0]);
+        assert_eq!("nhash 6", lines[1]);
+        assert_eq!("lottery 6 o", lines[2]);
+        assert_eq!("hashid o2fXhV", lines[lines.len() - 1]);
+    }
+}
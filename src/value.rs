@@ -0,0 +1,26 @@
+/// Conversions used by `HashIdValue` implementations, including derived
+/// ones. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use crate::harsh::{DecodeError, EncodeError};
+    use std::convert::TryFrom;
+
+    pub fn to_value<T>(field: T, index: usize) -> Result<u64, EncodeError>
+    where
+        u64: TryFrom<T>,
+    {
+        u64::try_from(field).map_err(|_| EncodeError::Range(index))
+    }
+
+    pub fn from_value<T: TryFrom<u64>>(values: &[u64], index: usize) -> Result<T, DecodeError> {
+        T::try_from(values[index]).map_err(|_| DecodeError::Range(index))
+    }
+
+    pub fn arity(values: &[u64], arity: usize) -> Result<(), DecodeError> {
+        if values.len() == arity {
+            Ok(())
+        } else {
+            Err(DecodeError::Arity(values.len()))
+        }
+    }
+}
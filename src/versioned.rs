@@ -0,0 +1,177 @@
+use crate::{
+    builder::BuildHarshError,
+    harsh::{DecodeError, Harsh, HarshError},
+};
+use std::collections::HashMap;
+
+/// A set of versioned `Harsh` configurations.
+///
+/// New hashids are always encoded with the current configuration, while
+/// decoding dispatches on the version tag following any prefix, so that
+/// ids issued under a previous salt or alphabet remain readable without
+/// trying every configuration in turn.
+#[derive(Clone, Debug)]
+pub struct VersionedHarsh {
+    current: u8,
+    generations: HashMap<u8, Harsh>,
+}
+
+impl VersionedHarsh {
+    /// Creates a `VersionedHarsh` encoding with `current` and decoding ids
+    /// produced by `current` or any of the `previous` configurations.
+    ///
+    /// Every configuration must have been built with a version, and no two
+    /// configurations may share a version or a version tag.
+    pub fn new<I>(current: Harsh, previous: I) -> Result<Self, BuildHarshError>
+    where
+        I: IntoIterator<Item = Harsh>,
+    {
+        let tag = current.version_tag().ok_or(BuildHarshError::Unversioned)?;
+
+        let mut generations = HashMap::new();
+        for harsh in Some(current).into_iter().chain(previous) {
+            let (version, tag) = match (harsh.version(), harsh.version_tag()) {
+                (Some(version), Some(tag)) => (version, tag),
+                _ => return Err(BuildHarshError::Unversioned),
+            };
+
+            if generations
+                .values()
+                .any(|other: &Harsh| other.version() == Some(version))
+                || generations.contains_key(&tag)
+            {
+                return Err(BuildHarshError::DuplicateVersion(version));
+            }
+
+            generations.insert(tag, harsh);
+        }
+
+        Ok(VersionedHarsh {
+            current: tag,
+            generations,
+        })
+    }
+
+    /// The configuration used to encode new hashids.
+    pub fn current(&self) -> &Harsh {
+        &self.generations[&self.current]
+    }
+
+    /// Encodes a slice of `u64` values with the current configuration.
+    pub fn encode(&self, values: &[u64]) -> String {
+        self.current().encode(values)
+    }
+
+    /// Decodes a hashid produced by any known configuration.
+    pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<Vec<u64>, HarshError> {
+        self.decode_versioned(input).map(|(_, values)| values)
+    }
+
+    /// Decodes a hashid produced by any known configuration, returning the
+    /// version of the configuration alongside the decoded values.
+    pub fn decode_versioned<T: AsRef<str>>(&self, input: T) -> Result<(u8, Vec<u64>), HarshError> {
+        let input = input.as_ref();
+
+        // The tag follows the prefix, which may differ between generations,
+        // so every generation whose prefix and tag match is a candidate.
+        // Longer prefixes are tried first, being the more specific match.
+        let mut candidates: Vec<_> = self
+            .generations
+            .iter()
+            .filter(|&(&tag, harsh)| {
+                let rest = match harsh.prefix() {
+                    Some(prefix) => input
+                        .strip_prefix(prefix)
+                        .and_then(|rest| rest.strip_prefix('_')),
+                    None => Some(input),
+                };
+                rest.and_then(|rest| rest.as_bytes().first()) == Some(&tag)
+            })
+            .map(|(_, harsh)| harsh)
+            .collect();
+        candidates.sort_by_key(|harsh| {
+            (
+                std::cmp::Reverse(harsh.prefix().map_or(0, str::len)),
+                harsh.version(),
+            )
+        });
+
+        let mut error = None;
+        for harsh in candidates {
+            let version = harsh.version().expect("versioned configuration");
+            match harsh.decode(input) {
+                Ok(values) => return Ok((version, values)),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        Err(error.unwrap_or(HarshError::Decode(DecodeError::Version)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VersionedHarsh;
+    use crate::Harsh;
+
+    #[test]
+    fn decodes_previous_generations() {
+        let old = Harsh::builder().salt("old").version(0).build().unwrap();
+        let new = Harsh::builder().salt("new").version(1).build().unwrap();
+        let old_id = old.encode(&[1, 2, 3]);
+
+        let versioned = VersionedHarsh::new(new, vec![old]).unwrap();
+        let new_id = versioned.encode(&[1, 2, 3]);
+
+        assert_ne!(old_id, new_id);
+        assert_eq!(
+            (0, vec![1, 2, 3]),
+            versioned.decode_versioned(&old_id).unwrap()
+        );
+        assert_eq!(
+            (1, vec![1, 2, 3]),
+            versioned.decode_versioned(&new_id).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let old = Harsh::builder().salt("old").version(0).build().unwrap();
+        let new = Harsh::builder().salt("new").version(1).build().unwrap();
+        let old_id = old.encode(&[1, 2, 3]);
+
+        let versioned = VersionedHarsh::new(new, None).unwrap();
+        assert!(versioned.decode(old_id).is_err());
+    }
+
+    #[test]
+    fn rejects_duplicate_versions() {
+        let a = Harsh::builder().salt("a").version(3).build().unwrap();
+        let b = Harsh::builder().salt("b").version(3).build().unwrap();
+        assert!(VersionedHarsh::new(a, vec![b]).is_err());
+    }
+
+    #[test]
+    fn decodes_prefixed_generations() {
+        let old = Harsh::builder().version(0).prefix("cus").build().unwrap();
+        let new = Harsh::builder().version(1).prefix("cus").build().unwrap();
+        let other = Harsh::builder().salt("other").version(2).build().unwrap();
+        let old_id = old.encode(&[1]);
+        let other_id = other.encode(&[2]);
+
+        let versioned = VersionedHarsh::new(new, vec![old, other]).unwrap();
+        let new_id = versioned.encode(&[3]);
+
+        assert!(old_id.starts_with("cus_"));
+        assert_eq!((0, vec![1]), versioned.decode_versioned(&old_id).unwrap());
+        assert_eq!((1, vec![3]), versioned.decode_versioned(&new_id).unwrap());
+        assert_eq!((2, vec![2]), versioned.decode_versioned(&other_id).unwrap());
+        assert!(versioned.decode(&old_id[4..]).is_err());
+    }
+
+    #[test]
+    fn requires_versioned_configurations() {
+        assert!(VersionedHarsh::new(Harsh::default(), None).is_err());
+    }
+}
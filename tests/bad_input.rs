@@ -1,4 +1,4 @@
-use harsh::Harsh;
+use harsh::{DecodeError, Harsh, HarshError};
 
 #[test]
 fn small_alphabet() {
@@ -28,6 +28,15 @@ fn should_fail_for_encoding_nothing() {
     );
 }
 
+#[test]
+fn should_return_empty_for_encoding_empty_hex() {
+    assert_eq!(
+        Ok(String::new()),
+        Harsh::default().encode_hex(""),
+        "should return an empty string when encoding empty hex"
+    );
+}
+
 #[test]
 #[should_panic]
 fn should_fail_for_decoding_nothing() {
@@ -51,3 +60,46 @@ fn should_fail_when_encoding_non_hex_input() {
 fn should_fail_when_hex_decoding_invalid_id() {
     Harsh::default().decode_hex("f").unwrap();
 }
+
+#[test]
+fn non_ascii_alphabet() {
+    assert!(
+        Harsh::builder()
+            .alphabet("abcdefghijklmnopqrstuvwxyzé")
+            .build()
+            .is_err(),
+        "should throw an error when alphabet includes non-ASCII characters"
+    );
+}
+
+#[test]
+fn exhausted_alphabet() {
+    assert!(
+        Harsh::builder()
+            .alphabet("abcdefghijklmnop")
+            .separators("bcdefghijklmnop")
+            .build()
+            .is_err(),
+        "should throw an error when separators leave too few characters"
+    );
+}
+
+#[test]
+fn overflowing_value() {
+    assert_eq!(
+        Err(HarshError::Decode(DecodeError::Overflow)),
+        Harsh::default().decode("5".repeat(40)),
+        "should throw an error when a value overflows"
+    );
+}
+
+#[test]
+fn nul_in_alphabet() {
+    assert!(
+        Harsh::builder()
+            .alphabet("\0abcdefghijklmnopqrstuvwxyz")
+            .build()
+            .is_err(),
+        "should throw an error when alphabet includes NUL"
+    );
+}
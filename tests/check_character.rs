@@ -0,0 +1,71 @@
+use harsh::{DecodeError, Harsh, HarshError};
+
+const NUMBERS: &[u64] = &[1, 2, 3];
+
+#[test]
+fn check_character_round_trip() {
+    for length in &[0, 10, 30] {
+        let harsh = Harsh::builder()
+            .salt("this is my salt")
+            .length(*length)
+            .version(3)
+            .check_character(true)
+            .build()
+            .unwrap();
+
+        let id = harsh.encode(NUMBERS);
+        assert_eq!(NUMBERS, &harsh.decode(&id).unwrap()[..]);
+    }
+}
+
+#[test]
+fn detects_every_substitution() {
+    let harsh = Harsh::builder()
+        .salt("this is my salt")
+        .length(12)
+        .check_character(true)
+        .build()
+        .unwrap();
+
+    let id = harsh.encode(NUMBERS);
+    let characters = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
+
+    for idx in 0..id.len() {
+        for c in characters
+            .chars()
+            .filter(|&c| Some(c) != id.chars().nth(idx))
+        {
+            let mut typo = id.clone();
+            typo.replace_range(idx..idx + 1, &c.to_string());
+            assert_check_error(&harsh, &typo);
+        }
+    }
+}
+
+#[test]
+fn detects_every_adjacent_transposition() {
+    let harsh = Harsh::builder()
+        .salt("this is my salt")
+        .length(12)
+        .check_character(true)
+        .build()
+        .unwrap();
+
+    let id = harsh.encode(NUMBERS);
+    for idx in 0..id.len() - 1 {
+        let mut typo = id.clone().into_bytes();
+        if typo[idx] == typo[idx + 1] {
+            continue;
+        }
+
+        typo.swap(idx, idx + 1);
+        assert_check_error(&harsh, &String::from_utf8(typo).unwrap());
+    }
+}
+
+fn assert_check_error(harsh: &Harsh, id: &str) {
+    match harsh.decode(id) {
+        Err(HarshError::Decode(DecodeError::Check)) => (),
+        other => panic!("expected check error for {}, got {:?}", id, other),
+    }
+}
@@ -0,0 +1,86 @@
+use arbitrary::Arbitrary;
+use harsh::{Dialect, Harsh, HarshBuilder};
+
+/// An arbitrary `HarshBuilder` configuration.
+///
+/// With `printable` set, the alphabet, separators, prefix and confusable
+/// classes are mapped to printable ASCII, so that many configurations build;
+/// otherwise they are passed on as they are.
+#[derive(Arbitrary, Clone, Debug)]
+pub struct Config {
+    salt: Vec<u8>,
+    alphabet: Option<Vec<u8>>,
+    separators: Option<Vec<u8>>,
+    printable: bool,
+    length: u16,
+    exact_length: bool,
+    version: Option<u8>,
+    prefix: Option<Vec<u8>>,
+    prefix_salt: bool,
+    check_character: bool,
+    confusables: Vec<Vec<u8>>,
+    max_input_length: Option<u16>,
+    max_values: Option<u8>,
+    max_value: Option<u64>,
+    legacy: bool,
+}
+
+impl Config {
+    pub fn builder(&self) -> HarshBuilder {
+        let text = |bytes: &[u8]| -> Vec<u8> {
+            if self.printable {
+                printable(bytes).into_bytes()
+            } else {
+                bytes.to_vec()
+            }
+        };
+
+        let mut builder = Harsh::builder()
+            .salt(self.salt.clone())
+            .prefix_salt(self.prefix_salt)
+            .check_character(self.check_character)
+            .confusables(self.confusables.iter().map(|class| text(class)));
+
+        builder = if self.exact_length {
+            builder.exact_length(self.length as usize)
+        } else {
+            builder.length(self.length as usize)
+        };
+
+        if let Some(alphabet) = &self.alphabet {
+            builder = builder.alphabet(text(alphabet));
+        }
+        if let Some(separators) = &self.separators {
+            builder = builder.separators(text(separators));
+        }
+        if let Some(version) = self.version {
+            builder = builder.version(version);
+        }
+        if let Some(prefix) = &self.prefix {
+            builder = builder.prefix(String::from_utf8_lossy(&text(prefix)));
+        }
+        if let Some(max) = self.max_input_length {
+            builder = builder.max_input_length(max as usize);
+        }
+        if let Some(max) = self.max_values {
+            builder = builder.max_values(max as usize);
+        }
+        if let Some(max) = self.max_value {
+            builder = builder.max_value(max);
+        }
+        if self.legacy {
+            builder = builder.dialect(Dialect::Legacy);
+        }
+
+        builder
+    }
+
+    pub fn build(&self) -> Option<Harsh> {
+        self.builder().build().ok()
+    }
+}
+
+/// Maps arbitrary bytes to printable ASCII, so that many alphabets are valid.
+pub fn printable(bytes: &[u8]) -> String {
+    bytes.iter().map(|u| (b'!' + u % 94) as char).collect()
+}
@@ -0,0 +1,18 @@
+//! Arbitrary input shared by the property tests and the fuzz targets.
+#![allow(dead_code, unused_imports)]
+
+mod config;
+
+pub use config::{printable, Config};
+
+use quickcheck::{Arbitrary, Gen};
+
+impl Arbitrary for Config {
+    fn arbitrary<G: Gen>(g: &mut G) -> Config {
+        // Draw the configuration from bytes, as the fuzz targets do, so that
+        // both share a single definition.
+        let bytes: Vec<u8> = (0..256).map(|_| u8::arbitrary(g)).collect();
+        let mut input = arbitrary::Unstructured::new(&bytes);
+        arbitrary::Arbitrary::arbitrary(&mut input).expect("Unable to create config")
+    }
+}
@@ -0,0 +1,92 @@
+use harsh::{BuildHarshError, Dialect, Harsh, HarshBuilder, HarshConfig};
+use std::{convert::TryFrom, env};
+
+#[test]
+fn config_matches_builder() {
+    let mut config = HarshConfig::default();
+    config.salt = Some("this is my salt".into());
+    config.length = 12;
+    config.prefix = Some("cus".into());
+
+    let from_config = Harsh::try_from(config).unwrap();
+    let from_builder = Harsh::builder()
+        .salt("this is my salt")
+        .length(12)
+        .prefix("cus")
+        .build()
+        .unwrap();
+
+    assert_eq!("cus_9LGlaHquq06D", from_config.encode(&[1, 2, 3]));
+    assert_eq!(
+        from_builder.encode(&[1, 2, 3]),
+        from_config.encode(&[1, 2, 3])
+    );
+}
+
+#[test]
+fn invalid_config_is_rejected() {
+    let mut config = HarshConfig::default();
+    config.alphabet = Some("1234567890".into());
+
+    assert!(Harsh::try_from(config).is_err());
+}
+
+#[test]
+fn builder_from_env() {
+    env::set_var("HARSH_TEST_ENV_SALT", "this is my salt");
+    env::set_var("HARSH_TEST_ENV_LENGTH", "8");
+
+    let harsh = HarshBuilder::from_env("HARSH_TEST_ENV")
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!("GlaHquq0", harsh.encode(&[1, 2, 3]));
+}
+
+#[test]
+fn dialect_from_env() {
+    env::set_var("HARSH_TEST_DIALECT_ENV_DIALECT", "legacy");
+    let config = HarshConfig::from_env("HARSH_TEST_DIALECT_ENV").unwrap();
+    assert_eq!(Dialect::Legacy, config.dialect);
+
+    env::set_var("HARSH_TEST_BAD_DIALECT_ENV_DIALECT", "hashids_php");
+    match HarshConfig::from_env("HARSH_TEST_BAD_DIALECT_ENV") {
+        Err(BuildHarshError::Environment(key)) => {
+            assert_eq!("HARSH_TEST_BAD_DIALECT_ENV_DIALECT", key)
+        }
+        other => panic!("expected environment error, got {:?}", other),
+    }
+
+    assert_eq!(Dialect::HashidsJs, HarshConfig::default().dialect);
+}
+
+#[test]
+fn unparseable_env_is_rejected() {
+    env::set_var("HARSH_TEST_BAD_ENV_LENGTH", "eight");
+
+    match HarshConfig::from_env("HARSH_TEST_BAD_ENV") {
+        Err(BuildHarshError::Environment(key)) => assert_eq!("HARSH_TEST_BAD_ENV_LENGTH", key),
+        other => panic!("expected environment error, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn config_from_json() {
+    let config: HarshConfig = serde_json::from_str(
+        r#"{ "salt": "this is my salt", "length": 8, "check_character": true }"#,
+    )
+    .unwrap();
+
+    assert_eq!(Some("this is my salt"), config.salt.as_deref());
+    assert_eq!(8, config.length);
+    assert!(config.check_character);
+    assert!(serde_json::from_str::<HarshConfig>(r#"{ "sault": "typo" }"#).is_err());
+
+    let legacy: HarshConfig = serde_json::from_str(r#"{ "dialect": "legacy" }"#).unwrap();
+    assert_eq!(Dialect::Legacy, legacy.dialect);
+
+    let round_trip: HarshConfig =
+        serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+    assert_eq!(config, round_trip);
+}
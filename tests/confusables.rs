@@ -0,0 +1,51 @@
+use harsh::{BuildHarshError, Harsh};
+
+const ALPHABET: &str = "abcdefghijkmnpqrstuvwxyzABCDEFGHJKLMNPQRTUVWXYZ0123456789";
+
+#[test]
+fn confusable_characters_are_normalized() {
+    let harsh = Harsh::builder()
+        .alphabet(ALPHABET)
+        .confusables(vec!["0Oo", "1lI", "5S"])
+        .build()
+        .unwrap();
+
+    for &values in &[&[0u64][..], &[1, 2, 3], &[1000000000000], &[5, 5, 5]] {
+        let id = harsh.encode(values);
+        let mistyped: String = id
+            .chars()
+            .map(|c| match c {
+                '0' => 'O',
+                '1' => 'l',
+                '5' => 'S',
+                c => c,
+            })
+            .collect();
+
+        assert_eq!(values, &harsh.decode(&mistyped).unwrap()[..]);
+    }
+}
+
+#[test]
+fn prefix_is_not_normalized() {
+    let harsh = Harsh::builder()
+        .alphabet(ALPHABET)
+        .prefix("Ol")
+        .confusables(vec!["0Oo", "1lI"])
+        .build()
+        .unwrap();
+
+    let id = harsh.encode(&[1, 2, 3]);
+    assert!(id.starts_with("Ol_"));
+    assert_eq!(&[1, 2, 3], &harsh.decode(&id).unwrap()[..]);
+}
+
+#[test]
+fn alphabet_with_two_confusables_is_rejected() {
+    let result = Harsh::builder().confusables(vec!["0Oo"]).build();
+
+    match result {
+        Err(BuildHarshError::Confusable(_, _)) => (),
+        other => panic!("expected confusable error, got {:?}", other),
+    }
+}
@@ -0,0 +1,91 @@
+//! Runs the conformance corpus in `tests/fixtures/corpus.json`, and the
+//! cases recorded from this crate in `tests/fixtures/recorded.json`.
+//!
+//! Both pin the output of this crate; see `tests/fixtures/README.md` for
+//! their format, their provenance and how to extend them.
+
+use harsh::{BuildHarshError, Dialect, Harsh};
+use serde_json::Value;
+
+const CORPUS_VERSION: u64 = 1;
+
+#[test]
+fn corpus() {
+    run_all(include_str!("fixtures/corpus.json"));
+}
+
+#[test]
+fn recorded() {
+    run_all(include_str!("fixtures/recorded.json"));
+}
+
+fn run_all(corpus: &str) {
+    let corpus: Value = serde_json::from_str(corpus).unwrap();
+    assert_eq!(Some(CORPUS_VERSION), corpus["version"].as_u64());
+
+    let cases = corpus["cases"].as_array().unwrap();
+    assert!(!cases.is_empty());
+
+    for case in cases {
+        run(case);
+    }
+}
+
+fn run(case: &Value) {
+    let harsh = match (build(&case["config"]), case["error"].as_str()) {
+        (Err(_), Some("build")) => return,
+        (Ok(_), Some("build")) => panic!("expected build error: {}", case),
+        (result, _) => result.unwrap_or_else(|e| panic!("{}: {}", e, case)),
+    };
+
+    let id = case["id"].as_str();
+    match (case["error"].as_str(), id) {
+        (Some("decode"), Some(id)) => assert!(harsh.decode(id).is_err(), "{}", case),
+        (Some("decode_hex"), Some(id)) => assert!(harsh.decode_hex(id).is_err(), "{}", case),
+        (Some("encode_hex"), _) => {
+            let hex = case["hex"].as_str().unwrap();
+            assert!(harsh.encode_hex(hex).is_err(), "{}", case);
+        }
+        (None, Some(id)) => {
+            if let Some(hex) = case["hex"].as_str() {
+                assert_eq!(id, harsh.encode_hex(hex).unwrap(), "{}", case);
+                assert_eq!(hex, harsh.decode_hex(id).unwrap(), "{}", case);
+            } else {
+                let values: Vec<u64> = case["values"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|value| value.as_u64().unwrap())
+                    .collect();
+
+                assert_eq!(id, harsh.encode(&values), "{}", case);
+                if !values.is_empty() {
+                    assert_eq!(values, harsh.decode(id).unwrap(), "{}", case);
+                }
+            }
+        }
+        _ => panic!("malformed case: {}", case),
+    }
+}
+
+fn build(config: &Value) -> Result<Harsh, BuildHarshError> {
+    let mut builder = Harsh::builder();
+    if let Some(salt) = config["salt"].as_str() {
+        builder = builder.salt(salt);
+    }
+    if let Some(alphabet) = config["alphabet"].as_str() {
+        builder = builder.alphabet(alphabet);
+    }
+    if let Some(separators) = config["separators"].as_str() {
+        builder = builder.separators(separators);
+    }
+    if let Some(length) = config["length"].as_u64() {
+        builder = builder.length(length as usize);
+    }
+    match config["dialect"].as_str() {
+        None => {}
+        Some("legacy") => builder = builder.dialect(Dialect::Legacy),
+        Some(dialect) => panic!("unknown dialect: {}", dialect),
+    }
+    builder.build()
+}
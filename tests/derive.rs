@@ -0,0 +1,51 @@
+#![cfg(feature = "derive")]
+
+use harsh::{DecodeError, EncodeError, Harsh, HarshError, HashIdValue};
+
+#[derive(Clone, Copy, Debug, PartialEq, HashIdValue)]
+struct TenantUserKey {
+    tenant: u32,
+    user: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, HashIdValue)]
+struct Offset(i16, u8);
+
+#[test]
+fn named_fields_round_trip() {
+    let harsh = Harsh::builder().salt("this is my salt").build().unwrap();
+    let key = TenantUserKey {
+        tenant: 7,
+        user: 9007199254740991,
+    };
+
+    let id = harsh.encode_as(&key).unwrap();
+    assert_eq!(harsh.encode(&[7, 9007199254740991]), id);
+    assert_eq!(Ok(key), harsh.decode_as(&id));
+}
+
+#[test]
+fn fields_are_validated() {
+    let harsh = Harsh::default();
+
+    let id = harsh.encode(&[1 << 32, 1]);
+    assert_eq!(
+        Err(HarshError::Decode(DecodeError::Range(0))),
+        harsh.decode_as::<TenantUserKey, _>(&id)
+    );
+
+    let id = harsh.encode(&[1, 2, 3]);
+    assert_eq!(
+        Err(HarshError::Decode(DecodeError::Arity(3))),
+        harsh.decode_as::<TenantUserKey, _>(&id)
+    );
+}
+
+#[test]
+fn tuple_structs_round_trip() {
+    let harsh = Harsh::default();
+
+    let id = harsh.encode_as(&Offset(300, 4)).unwrap();
+    assert_eq!(Ok(Offset(300, 4)), harsh.decode_as(&id));
+    assert_eq!(Err(EncodeError::Range(0)), harsh.encode_as(&Offset(-1, 4)));
+}
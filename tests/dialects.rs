@@ -0,0 +1,164 @@
+//! Edge cases in which Hashids ports are sometimes said to disagree.
+//!
+//! These tests pin the behavior of hashids.js, the only reference this crate
+//! is checked against, using examples published with it where available,
+//! and the floating point arithmetic of hashids.js 1.x that
+//! `Dialect::Legacy` reproduces.
+
+use harsh::{BuildHarshError, Dialect, Harsh};
+
+const DIALECTS: &[Dialect] = &[Dialect::HashidsJs, Dialect::Legacy];
+
+#[test]
+fn published_examples() {
+    let examples: &[(&str, usize, &[u64], &str)] = &[
+        ("", 0, &[1, 2, 3], "o2fXhV"),
+        ("this is my salt", 0, &[1, 2, 3], "laHquq"),
+        ("this is my salt", 0, &[12345], "NkK9"),
+        ("this is my salt", 0, &[683, 94108, 123, 5], "aBMswoO2UB3Sj"),
+        ("this is my salt", 8, &[1], "gB0NV05e"),
+        ("My Project", 0, &[1, 2, 3], "Z4UrtW"),
+        ("My Other Project", 0, &[1, 2, 3], "gPUasb"),
+        ("", 10, &[1], "VolejRejNm"),
+    ];
+
+    for &dialect in DIALECTS {
+        for &(salt, length, values, expected) in examples {
+            let harsh = Harsh::builder()
+                .salt(salt)
+                .length(length)
+                .dialect(dialect)
+                .build()
+                .unwrap();
+            assert_eq!(expected, harsh.encode(values), "{:?}", (dialect, salt));
+            assert_eq!(values, &harsh.decode(expected).unwrap()[..]);
+        }
+    }
+}
+
+#[test]
+fn hashids_js_is_the_default() {
+    assert_eq!(Dialect::HashidsJs, Dialect::default());
+    assert_eq!(Dialect::HashidsJs, Harsh::default().dialect());
+
+    let harsh = Harsh::builder()
+        .salt("this is my salt")
+        .dialect(Dialect::HashidsJs)
+        .build()
+        .unwrap();
+    assert_eq!(
+        Harsh::builder().salt("this is my salt").build().unwrap(),
+        harsh
+    );
+}
+
+#[test]
+fn hashids_js_encodes_large_values_exactly() {
+    let harsh = Harsh::default();
+    let values = [(1 << 53) + 1, u64::MAX];
+
+    assert_ne!(harsh.encode(&values[..1]), harsh.encode(&[1 << 53]));
+    assert_eq!(
+        &values[..],
+        &harsh.decode(harsh.encode(&values)).unwrap()[..]
+    );
+}
+
+#[test]
+fn legacy_rounds_large_values() {
+    // Every value here is exactly representable as a JavaScript number.
+    let examples: &[(&str, &[u64], &str)] = &[
+        ("", &[1 << 53], "mNWyy8yjQYE"),
+        ("", &[(1 << 53) + 2], "oXWyy8ylQjA"),
+        ("", &[1 << 60], "YkZM1Vrj7yp0"),
+        ("", &[1 << 63], "qZ1QEvgn7J7R2"),
+        ("", &[1_000_000_000_000_000_000], "glgmDqLP1n36"),
+        ("", &[1 << 63, 0, 1 << 60], "N0JOrqlBLPLD8src1PVk87Qr0Nl"),
+        ("this is my salt", &[(1 << 53) + 2], "lX4VVkV7jvP"),
+        ("this is my salt", &[1 << 60], "33KJ9k1mOWBR"),
+        ("this is my salt", &[1 << 63], "DxMP5qew6m6z1"),
+        (
+            "this is my salt",
+            &[1 << 63, 0, 1 << 60],
+            "Lq69DlewMOMVyHOUvMJNQLBpel1",
+        ),
+    ];
+
+    for &(salt, values, expected) in examples {
+        let harsh = Harsh::builder()
+            .salt(salt)
+            .dialect(Dialect::Legacy)
+            .build()
+            .unwrap();
+        assert_eq!(expected, harsh.encode(values), "{:?}", (salt, values));
+        assert_eq!(values, &harsh.decode(expected).unwrap()[..]);
+    }
+
+    // Values that are not JavaScript numbers are rounded to the nearest one.
+    let harsh = Harsh::builder().dialect(Dialect::Legacy).build().unwrap();
+    assert_eq!("mNWyy8yjQYE", harsh.encode(&[(1 << 53) + 1]));
+    assert_eq!("BEpR8m5g8VnoX", harsh.encode(&[u64::MAX]));
+    assert_eq!(harsh.encode(&[u64::MAX]), harsh.encode(&[u64::MAX - 1000]));
+}
+
+#[test]
+fn legacy_rejects_ids_that_decode_to_two_to_the_64() {
+    let harsh = Harsh::builder().dialect(Dialect::Legacy).build().unwrap();
+
+    assert_eq!("N1j5EBqxElY6z", harsh.encode(&[u64::MAX - 2047]));
+    assert!(harsh.decode("N1j5EBqxElY6z").is_err());
+    assert!(harsh.decode("BEpR8m5g8VnoX").is_err());
+}
+
+#[test]
+fn separators_are_borrowed_from_the_alphabet() {
+    // Without separators, ceil(alphabet / 3.5) characters become separators.
+    let harsh = Harsh::builder()
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .separators("")
+        .build()
+        .unwrap();
+
+    assert_eq!(8, harsh.separators().len());
+    assert_eq!(16, harsh.alphabet().len());
+    assert_eq!(2, harsh.guards().len());
+    assert_eq!(
+        "mdfphx",
+        Harsh::builder()
+            .alphabet("abcdefghijklmnopqrstuvwxyz")
+            .build()
+            .unwrap()
+            .encode(&[1, 2, 3])
+    );
+}
+
+#[test]
+fn guards_come_from_separators_for_tiny_alphabets() {
+    // Only `a` and `b` remain once the separators are removed, so the guard
+    // is taken from the separators rather than the alphabet.
+    let separators = "cdefghijklmnop";
+    let harsh = Harsh::builder()
+        .alphabet("abcdefghijklmnop")
+        .separators(separators)
+        .build()
+        .unwrap();
+
+    assert_eq!(2, harsh.alphabet().len());
+    assert_eq!(1, harsh.guards().len());
+    assert_eq!(13, harsh.separators().len());
+    assert!(separators.as_bytes().contains(&harsh.guards()[0]));
+
+    let id = harsh.encode(&[1, 2, 3]);
+    assert_eq!(vec![1, 2, 3], harsh.decode(id).unwrap());
+}
+
+#[test]
+fn spaces_are_rejected() {
+    let result = Harsh::builder()
+        .alphabet("abcdefghijklmnop qrstuvwxyz")
+        .build();
+    assert!(matches!(
+        result,
+        Err(BuildHarshError::IllegalCharacter(' '))
+    ));
+}
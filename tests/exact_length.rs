@@ -0,0 +1,57 @@
+use harsh::{Dialect, EncodeError, Harsh};
+
+#[test]
+fn exact_length_ids_have_exact_width() {
+    for width in 2..8 {
+        let builder = Harsh::builder().salt("this is my salt").exact_length(width);
+        let max = builder.max_exact_value().unwrap().unwrap();
+        let harsh = builder.build().unwrap();
+
+        for &value in &[0, 1, max / 2, max] {
+            let id = harsh.try_encode(&[value]).unwrap();
+            assert_eq!(width, id.len(), "{} at width {}", value, width);
+            assert_eq!(&[value], &harsh.decode(&id).unwrap()[..]);
+        }
+
+        if let Some(value) = max.checked_add(1) {
+            match harsh.try_encode(&[value]) {
+                Err(EncodeError::Length) => (),
+                other => panic!("expected length error, got {:?}", other),
+            }
+            assert_eq!("", harsh.encode(&[value]));
+        }
+    }
+}
+
+#[test]
+fn max_exact_value() {
+    // The default alphabet has 44 characters left after separators and guards.
+    let builder = Harsh::builder().exact_length(3);
+    assert_eq!(Some(44 * 44 - 1), builder.max_exact_value().unwrap());
+
+    let builder = Harsh::builder().exact_length(1);
+    assert_eq!(None, builder.max_exact_value().unwrap());
+
+    let builder = Harsh::builder().exact_length(20);
+    assert_eq!(Some(u64::MAX), builder.max_exact_value().unwrap());
+}
+
+#[test]
+fn legacy_max_exact_value() {
+    // Values just below 44^10 round up to it, which takes one more digit.
+    let builder = Harsh::builder().dialect(Dialect::Legacy).exact_length(11);
+    let max = builder.max_exact_value().unwrap().unwrap();
+    let harsh = builder.build().unwrap();
+
+    assert!(max < 44u64.pow(10) - 1);
+    assert_eq!(11, harsh.try_encode(&[max]).unwrap().len());
+    assert_eq!(11, harsh.encoded_len(&[max]));
+    assert_eq!(Err(EncodeError::Length), harsh.try_encode(&[max + 1]));
+    assert_eq!(0, harsh.encoded_len(&[max + 1]));
+}
+
+#[test]
+fn minimum_length_is_not_exact() {
+    let harsh = Harsh::builder().length(2).build().unwrap();
+    assert_eq!(11, harsh.try_encode(&[9007199254740991]).unwrap().len());
+}
@@ -0,0 +1,280 @@
+// Generates corpus.json from the reference hashids.js implementation.
+//
+//     npm install
+//     npm run generate
+//
+// Every id is produced by hashids.js. Values above Number.MAX_SAFE_INTEGER
+// are passed as BigInt, and each case is decoded again so that an id
+// hashids.js cannot read back is never recorded. See README.md for the
+// format of the output.
+
+'use strict'
+
+const Hashids = require('hashids').default || require('hashids')
+const LegacyHashids = require('hashids-legacy')
+
+const VERSION = 1
+
+const SYMBOLS = '`~!@#$%^&*()-_=+\\|\'";:/?.>,<{[}]'
+
+// Keys are listed in the order they are written to the corpus.
+const CONFIGS = [
+  {},
+  { salt: 'this is my salt' },
+  { salt: 'this is my salt', length: 8 },
+  { length: 10 },
+  {
+    salt: 'this is my salt',
+    alphabet: 'xzal86grmb4jhysfoqp3we7291kuct5iv0nd',
+    length: 30,
+  },
+  { alphabet: 'cCsSfFhHuUiItT01' },
+  { alphabet: 'abdegjklCFHISTUc' },
+  { alphabet: 'abdegjklmnopqrSF' },
+  { alphabet: 'abdegjklmnopqrvwxyzABDEGJKLMNOPQRVWXYZ1234567890' },
+  {
+    alphabet:
+      'abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890' + SYMBOLS,
+  },
+  { alphabet: SYMBOLS },
+  { alphabet: 'abcdefghijklmnopqrstuvwxyz' },
+  { alphabet: 'abcdefghijklmnop', separators: 'cdefghijklmnop' },
+  { salt: 'this is my salt', separators: 'xyz' },
+  { salt: 'this is my salt', separators: '' },
+  { salt: 'this is my salt', length: 1 },
+  { salt: 'this is my salt', length: 64 },
+  { salt: SYMBOLS },
+]
+
+const MAX_U64 = 18446744073709551615n
+
+const VALUES = [
+  [0n],
+  [1n],
+  [12345n],
+  [928728n],
+  [1n, 2n, 3n],
+  [1n, 0n, 0n],
+  [0n, 0n, 1n],
+  [5n, 5n, 5n],
+  [683n, 94108n, 123n, 5n],
+  [9007199254740991n],
+  [10000000000n, 0n, 0n, 0n, 999999999999999n],
+  [9007199254740992n],
+  [4294967295n],
+  [MAX_U64],
+  [MAX_U64, 0n, MAX_U64],
+  Array.from({ length: 20 }, (_, index) => BigInt(index + 1)),
+]
+
+const HEXES = [
+  'deadbeef',
+  'abcdef123456',
+  '507f1f77bcf86cd799439011',
+  'f000000000000000000000000000000000000000000000000000f',
+]
+
+// Inputs of the hashids.js test suite that are not covered above, run
+// against the default configuration and the one with a custom alphabet.
+const SUITE_VALUES = [
+  [0n, 0n, 0n],
+  [1000000000000n],
+  Array.from({ length: 12 }, () => 5n),
+  [9007199254740991n, 9007199254740991n, 9007199254740991n],
+  [1000000001n, 1000000002n, 1000000003n, 1000000004n, 1000000005n],
+]
+
+const SUITE_HEXES = [
+  'abcddd6666ddeeeeeeeee',
+  'f00000fddddddeeeee4444444ababab',
+  'abcdef123456abcdef123456abcdef123456',
+  'fffffffffffffffffffffffffffffffffffffffffffffffffffff',
+]
+
+// Run against hashids.js 1.x, which computes with JavaScript numbers. Every
+// value is exactly representable as one, so each id decodes to its input.
+const LEGACY_CONFIGS = [
+  { dialect: 'legacy' },
+  { salt: 'this is my salt', dialect: 'legacy' },
+]
+
+const LEGACY_VALUES = [
+  [9007199254740994n],
+  [1152921504606846976n],
+  [9223372036854775808n],
+  [1000000000000000000n],
+  [9223372036854775808n, 0n, 1152921504606846976n],
+]
+
+const BUILD_ERRORS = [
+  { alphabet: '1234567890' },
+  { alphabet: 'a cdefghijklmnopqrstuvwxyz' },
+]
+
+const DECODE_ERRORS = [
+  [{}, ''],
+  [{}, 'f'],
+  [{}, 'o2fXhv'],
+  [{}, 'o2fXhVX'],
+  [{}, 'gYcxc'],
+  [{}, 'jR '],
+  [{}, '12345678901234567890'],
+  [{}, 'lEW77X7g527lEW77X7g527lEW77X7g527lEW77X7g527'],
+]
+
+const ENCODE_HEX_ERRORS = [[{}, 'z']]
+
+const DECODE_HEX_ERRORS = [[{}, 'f']]
+
+const SALTED_DECODE_ERRORS = [[{ salt: 'this is my salt' }, 'o2fXhV']]
+
+function hashids(config) {
+  if (config.dialect === 'legacy') {
+    return legacy(config)
+  }
+
+  const args = [config.salt || '', config.length || 0]
+  if (config.alphabet !== undefined || config.separators !== undefined) {
+    args.push(config.alphabet)
+  }
+  if (config.separators !== undefined) {
+    args.push(config.separators)
+  }
+  return new Hashids(...args)
+}
+
+// hashids.js 1.x takes JavaScript numbers, so values are converted on the way
+// in and back to BigInt on the way out.
+function legacy(config) {
+  const harsh = new LegacyHashids(config.salt || '', config.length || 0)
+  return {
+    encode: (values) => harsh.encode(values.map(Number)),
+    decode: (id) => harsh.decode(id),
+  }
+}
+
+// hashids.js signals invalid ids either by throwing or by returning an empty
+// result, depending on the version and the input.
+function attempt(operation) {
+  try {
+    return operation()
+  } catch (error) {
+    return undefined
+  }
+}
+
+function encode(config, values) {
+  const harsh = hashids(config)
+  const id = harsh.encode(values)
+  const decoded = (attempt(() => harsh.decode(id)) || []).map(BigInt)
+  if (values.length && decoded.join() !== values.join()) {
+    throw new Error(`${id} does not decode to ${values}`)
+  }
+  return { config, values, id }
+}
+
+function encodeHex(config, hex) {
+  const harsh = hashids(config)
+  const id = harsh.encodeHex(hex)
+  if (harsh.decodeHex(id) !== hex) {
+    throw new Error(`${id} does not decode to ${hex}`)
+  }
+  return { config, hex, id }
+}
+
+function buildError(config) {
+  if (attempt(() => hashids(config)) !== undefined) {
+    throw new Error(`expected ${JSON.stringify(config)} to be rejected`)
+  }
+  return { config, error: 'build' }
+}
+
+function decodeError(config, id) {
+  const decoded = attempt(() => hashids(config).decode(id))
+  if (decoded !== undefined && decoded.length) {
+    throw new Error(`expected ${JSON.stringify(id)} to be rejected`)
+  }
+  return { config, id, error: 'decode' }
+}
+
+function encodeHexError(config, hex) {
+  if (attempt(() => hashids(config).encodeHex(hex))) {
+    throw new Error(`expected ${JSON.stringify(hex)} to be rejected`)
+  }
+  return { config, hex, error: 'encode_hex' }
+}
+
+function decodeHexError(config, id) {
+  if (attempt(() => hashids(config).decodeHex(id))) {
+    throw new Error(`expected ${JSON.stringify(id)} to be rejected`)
+  }
+  return { config, id, error: 'decode_hex' }
+}
+
+// Formats a case on a single line, writing BigInt values as plain JSON
+// numbers.
+function format(value) {
+  if (typeof value === 'bigint') {
+    return value.toString()
+  }
+  if (Array.isArray(value)) {
+    return `[${value.map(format).join(', ')}]`
+  }
+  if (typeof value === 'object') {
+    const fields = Object.keys(value).map(
+      (key) => `${JSON.stringify(key)}: ${format(value[key])}`
+    )
+    return `{${fields.join(', ')}}`
+  }
+  return JSON.stringify(value)
+}
+
+const cases = []
+
+for (const config of CONFIGS) {
+  for (const values of VALUES) {
+    cases.push(encode(config, values))
+  }
+  for (const hex of HEXES) {
+    cases.push(encodeHex(config, hex))
+  }
+}
+
+for (const config of BUILD_ERRORS) {
+  cases.push(buildError(config))
+}
+
+cases.push(encode({}, []))
+for (const [config, id] of DECODE_ERRORS) {
+  cases.push(decodeError(config, id))
+}
+for (const [config, hex] of ENCODE_HEX_ERRORS) {
+  cases.push(encodeHexError(config, hex))
+}
+for (const [config, id] of DECODE_HEX_ERRORS) {
+  cases.push(decodeHexError(config, id))
+}
+for (const [config, id] of SALTED_DECODE_ERRORS) {
+  cases.push(decodeError(config, id))
+}
+
+for (const config of [CONFIGS[0], CONFIGS[4]]) {
+  for (const values of SUITE_VALUES) {
+    cases.push(encode(config, values))
+  }
+  for (const hex of SUITE_HEXES) {
+    cases.push(encodeHex(config, hex))
+  }
+}
+
+for (const config of LEGACY_CONFIGS) {
+  for (const values of LEGACY_VALUES) {
+    cases.push(encode(config, values))
+  }
+}
+
+process.stdout.write(
+  `{\n  "version": ${VERSION},\n  "cases": [\n` +
+    cases.map((entry) => `    ${format(entry)}`).join(',\n') +
+    '\n  ]\n}\n'
+)
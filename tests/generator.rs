@@ -0,0 +1,58 @@
+use harsh::{Generator, Harsh, MockClock};
+use std::{collections::HashSet, sync::Arc, thread};
+
+#[test]
+fn ids_are_unique_across_threads_and_workers() {
+    let generators: Vec<_> = (0..4)
+        .map(|worker| {
+            Arc::new(
+                Generator::builder(Harsh::default())
+                    .worker(worker)
+                    .bits(41, 2, 4)
+                    .clock(MockClock::new(1_600_000_000_000))
+                    .build()
+                    .unwrap(),
+            )
+        })
+        .collect();
+
+    let handles: Vec<_> = generators
+        .iter()
+        .flat_map(|generator| (0..4).map(move |_| Arc::clone(generator)))
+        .map(|generator| {
+            thread::spawn(move || {
+                (0..100)
+                    .map(|_| generator.generate().unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut ids = HashSet::new();
+    for handle in handles {
+        ids.extend(handle.join().unwrap());
+    }
+    assert_eq!(1600, ids.len());
+}
+
+#[test]
+fn values_increase_per_worker() {
+    let generator = Generator::builder(Harsh::default())
+        .clock(MockClock::new(1_600_000_000_000))
+        .build()
+        .unwrap();
+
+    let mut previous = generator.next_value().unwrap();
+    for step in 0..1000u64 {
+        if step % 7 == 0 {
+            generator.clock().advance(1);
+        }
+        if step % 100 == 0 {
+            generator.clock().set(1_600_000_000_000);
+        }
+
+        let next = generator.next_value().unwrap();
+        assert!(next > previous);
+        previous = next;
+    }
+}
@@ -0,0 +1,37 @@
+use harsh::Harsh;
+
+#[test]
+fn accessors() {
+    let harsh = Harsh::builder()
+        .salt("this is my salt")
+        .length(8)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        b"5N6y2rljDQak4xgzn8ZR1oKYLmJpEbVq3OBv9WwXPMe7",
+        harsh.alphabet()
+    );
+    assert_eq!(b"UHuhtcITCsFifS", harsh.separators());
+    assert_eq!(b"AdG0", harsh.guards());
+    assert_eq!(8, harsh.min_length());
+}
+
+#[test]
+fn preview_matches_build() {
+    let builder = Harsh::builder()
+        .alphabet("abcdefghijklmnopqrstuvwxyz")
+        .salt("this is my salt");
+
+    let preview = builder.preview().unwrap();
+    let harsh = builder.build().unwrap();
+
+    assert_eq!(harsh.alphabet(), preview.alphabet());
+    assert_eq!(harsh.separators(), preview.separators());
+    assert_eq!(harsh.guards(), preview.guards());
+}
+
+#[test]
+fn preview_reports_errors() {
+    assert!(Harsh::builder().alphabet("1234567890").preview().is_err());
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "keyed")]
+
+#[macro_use]
+extern crate quickcheck;
+
+use harsh::{Harsh, PermutedHarsh, SignedHarsh};
+use quickcheck::TestResult;
+
+quickcheck! {
+    fn permuted_always_decodable(key: Vec<u8>, width: u8, numbers: Vec<u64>) -> TestResult {
+        let width = u32::from(width % 63) + 2;
+        let harsh = match PermutedHarsh::with_width(Harsh::default(), key, width) {
+            Ok(harsh) => harsh,
+            Err(_) => return TestResult::discard(),
+        };
+        let numbers: Vec<_> = numbers.into_iter().map(|n| n >> (64 - width)).collect();
+        if numbers.is_empty() {
+            return TestResult::discard();
+        }
+        let encoded = harsh.encode(&numbers);
+        assert_eq!(numbers, harsh.decode(encoded).expect("Unable to decode value"));
+        TestResult::passed()
+    }
+}
+
+quickcheck! {
+    fn signed_always_decodable(secret: Vec<u8>, tag_length: u8, numbers: Vec<u64>, context: Vec<u8>) -> TestResult {
+        let tag_length = usize::from(tag_length % 8) + 1;
+        let harsh = match SignedHarsh::with_tag_length(Harsh::default(), secret, tag_length) {
+            Ok(harsh) => harsh,
+            Err(_) => return TestResult::discard(),
+        };
+        if numbers.is_empty() {
+            return TestResult::discard();
+        }
+        let encoded = harsh.encode_with_context(&numbers, &context).expect("Unable to encode value");
+        assert_eq!(numbers, harsh.decode_with_context(encoded, &context).expect("Unable to decode value"));
+        TestResult::passed()
+    }
+}
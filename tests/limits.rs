@@ -0,0 +1,53 @@
+use harsh::{DecodeError, Harsh, HarshError};
+
+#[test]
+fn max_input_length() {
+    let harsh = Harsh::builder().max_input_length(8).build().unwrap();
+
+    assert!(harsh.decode(harsh.encode(&[1, 2, 3])).is_ok());
+    assert_decode_error(&harsh, &"a".repeat(1 << 20), DecodeError::InputLength);
+    assert_decode_error(
+        &harsh,
+        &harsh.encode(&[1, 2, 3, 4, 5]),
+        DecodeError::InputLength,
+    );
+}
+
+#[test]
+fn max_values() {
+    let harsh = Harsh::builder().max_values(3).build().unwrap();
+
+    assert!(harsh.decode(harsh.encode(&[1, 2, 3])).is_ok());
+    assert_decode_error(
+        &harsh,
+        &harsh.encode(&[1, 2, 3, 4]),
+        DecodeError::ValueCount,
+    );
+    assert_decode_error(
+        &harsh,
+        &format!("x{}", "yc".repeat(1000)),
+        DecodeError::ValueCount,
+    );
+}
+
+#[test]
+fn max_value() {
+    let harsh = Harsh::builder().max_value(1000).build().unwrap();
+
+    assert!(harsh.decode(harsh.encode(&[1000, 0])).is_ok());
+    assert_decode_error(&harsh, &harsh.encode(&[1001]), DecodeError::ValueMagnitude);
+    assert_decode_error(
+        &harsh,
+        &harsh.encode(&[5, 1 << 40]),
+        DecodeError::ValueMagnitude,
+    );
+}
+
+fn assert_decode_error(harsh: &Harsh, id: &str, expected: DecodeError) {
+    assert_eq!(
+        Err(HarshError::Decode(expected)),
+        harsh.decode(id),
+        "{}",
+        id
+    );
+}
@@ -0,0 +1,171 @@
+//! No public API may panic for any configuration that builds.
+
+#[macro_use]
+extern crate quickcheck;
+
+mod common;
+
+use common::{printable, Config};
+use harsh::{Generator, IdLayout, MockClock, VersionedHarsh};
+
+quickcheck! {
+    fn builder_no_panic(config: Config) -> () {
+        let builder = config.builder();
+        let _ = builder.preview();
+        let _ = builder.max_exact_value();
+        let _ = builder.build();
+    }
+}
+
+quickcheck! {
+    fn encode_no_panic(config: Config, values: Vec<u64>, hex: String) -> () {
+        if let Some(harsh) = config.build() {
+            let _ = harsh.encode(&values);
+            let _ = harsh.try_encode(&values);
+            let _ = harsh.encode_traced(&values);
+            let _ = harsh.encode_hex(&hex);
+            let _ = harsh.encoded_len(&values);
+            let _ = harsh.max_encoded_len(values.len());
+            let _ = harsh.max_encoded_len(usize::MAX);
+            let _ = harsh.capacity();
+            #[cfg(feature = "fingerprint")]
+            let _ = harsh.fingerprint();
+        }
+    }
+}
+
+quickcheck! {
+    fn decode_no_panic(config: Config, values: Vec<u64>, input: String) -> () {
+        if let Some(harsh) = config.build() {
+            let encoded = harsh.encode(&values);
+            for input in &[&encoded, &input] {
+                let _ = harsh.decode(input);
+                let _ = harsh.decode_hex(input);
+                let _ = harsh.decode_detailed(input);
+            }
+        }
+    }
+}
+
+quickcheck! {
+    fn suggest_no_panic(config: Config, values: Vec<u64>, idx: usize) -> () {
+        if let Some(harsh) = config.build() {
+            let mut typo = harsh.encode(&values[..values.len().min(2)]).into_bytes();
+            if !typo.is_empty() {
+                let idx = idx % typo.len();
+                typo.remove(idx);
+            }
+            let _ = harsh.suggest(&String::from_utf8_lossy(&typo));
+        }
+    }
+}
+
+quickcheck! {
+    fn versioned_no_panic(current: Config, previous: Config, input: String) -> () {
+        if let (Some(current), Some(previous)) = (current.build(), previous.build()) {
+            if let Ok(versioned) = VersionedHarsh::new(current, Some(previous)) {
+                let _ = versioned.decode(versioned.encode(&[1, 2, 3]));
+                let _ = versioned.decode_versioned(&input);
+            }
+        }
+    }
+}
+
+quickcheck! {
+    fn set_no_panic(config: Config, values: Vec<u64>, input: String) -> () {
+        if let Some(harsh) = config.build() {
+            let encoded = harsh.encode_set(&values).unwrap_or_default();
+            for input in &[&encoded, &input] {
+                let _ = harsh.decode_set(input);
+            }
+        }
+    }
+}
+
+quickcheck! {
+    fn arity_no_panic(config: Config, value: u64, values: Vec<u64>, input: String) -> () {
+        if let Some(harsh) = config.build() {
+            let encoded = harsh.encode(&values);
+            for input in &[&harsh.encode_one(value), &encoded, &input] {
+                let _ = harsh.decode_one(input);
+                let _ = harsh.decode_array::<3, _>(input);
+                let _ = harsh.decode_as::<(u8, u64), _>(input);
+            }
+            let _ = harsh.encode_as(&(value, value as u8, value as i64));
+        }
+    }
+}
+
+quickcheck! {
+    fn layout_no_panic(config: Config, widths: Vec<u8>, values: Vec<u64>, input: String) -> () {
+        let fields: Vec<_> = widths
+            .iter()
+            .enumerate()
+            .map(|(idx, &bits)| (printable(&[idx as u8]), bits as u32))
+            .collect();
+        if let (Some(harsh), Ok(layout)) = (config.build(), IdLayout::new(&fields)) {
+            let packed = layout.pack(&values).unwrap_or_default();
+            let _ = layout.unpack(packed);
+            let _ = layout.get(packed, &printable(&[0]));
+
+            let encoded = harsh.encode_packed(&layout, &values).unwrap_or_default();
+            for input in &[&encoded, &input] {
+                let _ = harsh.decode_packed(&layout, input);
+            }
+        }
+    }
+}
+
+quickcheck! {
+    fn generator_no_panic(config: Config, epoch: u64, worker: u64, bits: (u8, u8, u8), now: u64, input: String) -> () {
+        if let Some(harsh) = config.build() {
+            let generator = Generator::builder(harsh)
+                .epoch(epoch)
+                .worker(worker)
+                .bits(bits.0 as u32, bits.1 as u32, bits.2 as u32)
+                .clock(MockClock::new(now))
+                .build();
+            if let Ok(generator) = generator {
+                let encoded = generator.generate().unwrap_or_default();
+                let _ = generator.next_value();
+                for input in &[&encoded, &input] {
+                    let _ = generator.parse(input);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "keyed")]
+quickcheck! {
+    fn permuted_no_panic(config: Config, key: Vec<u8>, width: u8, values: Vec<u64>, input: String) -> () {
+        use harsh::PermutedHarsh;
+
+        if let Some(harsh) = config.build() {
+            if let Ok(harsh) = PermutedHarsh::with_width(harsh, key, width as u32) {
+                let encoded = harsh.encode(&values);
+                for input in &[&encoded, &input] {
+                    let _ = harsh.decode(input);
+                }
+                let _ = values.first().map(|&value| harsh.unpermute(value));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "keyed")]
+quickcheck! {
+    fn signed_no_panic(config: Config, secret: Vec<u8>, tag_length: u8, values: Vec<u64>, context: Vec<u8>, input: String) -> () {
+        use harsh::SignedHarsh;
+
+        if let Some(harsh) = config.build() {
+            if let Ok(harsh) = SignedHarsh::with_tag_length(harsh, secret, tag_length as usize) {
+                let encoded = harsh.encode_with_context(&values, &context).unwrap_or_default();
+                for input in &[&encoded, &input] {
+                    let _ = harsh.decode(input);
+                    let _ = harsh.decode_with_context(input, &context);
+                }
+            }
+        }
+    }
+}
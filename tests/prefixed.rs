@@ -0,0 +1,47 @@
+use harsh::{DecodeError, Harsh, HarshError};
+
+#[test]
+fn prefix_is_rendered_and_stripped() {
+    let harsh = Harsh::builder().prefix("cus").build().unwrap();
+    let id = harsh.encode(&[1, 2, 3]);
+
+    assert_eq!("cus_o2fXhV", id);
+    assert_eq!(&[1, 2, 3], &harsh.decode(&id).unwrap()[..]);
+}
+
+#[test]
+fn wrong_prefix_is_rejected() {
+    let customers = Harsh::builder().prefix("cus").build().unwrap();
+    let orders = Harsh::builder().prefix("ord").build().unwrap();
+    let id = orders.encode(&[1, 2, 3]);
+
+    match customers.decode(&id) {
+        Err(HarshError::Decode(DecodeError::Prefix)) => (),
+        other => panic!("expected prefix error, got {:?}", other),
+    }
+
+    match customers.decode("o2fXhV") {
+        Err(HarshError::Decode(DecodeError::Prefix)) => (),
+        other => panic!("expected prefix error, got {:?}", other),
+    }
+}
+
+#[test]
+fn prefix_salt_differs_per_prefix() {
+    let base = Harsh::builder().salt("this is my salt").prefix_salt(true);
+    let customers = base.clone().prefix("cus").build().unwrap();
+    let orders = base.prefix("ord").build().unwrap();
+
+    let customer = customers.encode(&[1, 2, 3]);
+    let order = orders.encode(&[1, 2, 3]);
+    assert_ne!(customer["cus_".len()..], order["ord_".len()..]);
+
+    let forged = format!("cus_{}", &order["ord_".len()..]);
+    assert!(customers.decode(forged).is_err());
+}
+
+#[test]
+fn invalid_prefixes_are_rejected() {
+    assert!(Harsh::builder().prefix("").build().is_err());
+    assert!(Harsh::builder().prefix("cus_").build().is_err());
+}
@@ -0,0 +1,33 @@
+use harsh::{Alphabet, Harsh};
+
+const TEST_CASES: [(Alphabet, [&str; 3]); 6] = [
+    (Alphabet::NoLookalikes, ["Ej", "ReFaHW", "EPePAgjvJ"]),
+    (Alphabet::Lowercase, ["b5", "25cjin", "bgrl555555"]),
+    (Alphabet::Uppercase, ["B2", "26COIR", "B0LA222222"]),
+    (Alphabet::Crockford, ["E0", "WPCJSE", "E31PPY887E"]),
+    (Alphabet::UrlSafe, ["L8", "RwHMf5", "LQ7LY7RoJ"]),
+    (Alphabet::Consonants, ["qg", "5ghbs5", "qq3q9gggggg"]),
+];
+
+const VALUES: [&[u64]; 3] = [&[0], &[1, 2, 3], &[1000000000000]];
+
+#[test]
+fn presets() {
+    for &(preset, hashes) in &TEST_CASES {
+        let harsh = Harsh::builder()
+            .preset(preset)
+            .salt("this is my salt")
+            .build()
+            .unwrap();
+
+        for (&hash, &values) in hashes.iter().zip(VALUES.iter()) {
+            assert_eq!(hash, harsh.encode(values), "{:?}", preset);
+            assert_eq!(values, &harsh.decode(hash).unwrap()[..], "{:?}", preset);
+            assert!(
+                hash.chars().all(|c| preset.characters().contains(c)),
+                "{:?}",
+                preset
+            );
+        }
+    }
+}
@@ -1,7 +1,12 @@
 #[macro_use]
 extern crate quickcheck;
 
-use harsh::Harsh;
+mod common;
+
+use common::Config;
+use harsh::{DecodeError, Dialect, Harsh, HarshError};
+#[cfg(feature = "keyed")]
+use harsh::{PermutedHarsh, SignedHarsh};
 use quickcheck::TestResult;
 
 quickcheck! {
@@ -34,3 +39,97 @@ quickcheck! {
         TestResult::passed()
     }
 }
+
+quickcheck! {
+    fn encoded_len_matches_encode(numbers: Vec<u64>, min_length: u8) -> () {
+        let harsh = Harsh::builder().length(min_length as usize).build().expect("Unable to create harsh");
+        assert_eq!(harsh.encode(&numbers).len(), harsh.encoded_len(&numbers));
+    }
+}
+
+quickcheck! {
+    fn decode_no_panic_with_any_config(config: Config, encoded: String) -> () {
+        if let Some(harsh) = config.build() {
+            let _ = harsh.decode(&encoded);
+            let _ = harsh.decode_hex(&encoded);
+        }
+    }
+}
+
+quickcheck! {
+    fn encode_always_decodable_with_any_config(config: Config, numbers: Vec<u64>) -> TestResult {
+        let harsh = match config.build() {
+            Some(harsh) if !numbers.is_empty() => harsh,
+            _ => return TestResult::discard(),
+        };
+        let encoded = match harsh.try_encode(&numbers) {
+            Ok(encoded) => encoded,
+            Err(_) => return TestResult::discard(),
+        };
+
+        // The legacy dialect rounds values above 2^53, so they do not round-trip.
+        if harsh.dialect() == Dialect::Legacy && numbers.iter().any(|&value| value > 1 << 53) {
+            return TestResult::discard();
+        }
+
+        match harsh.decode(&encoded) {
+            Ok(decoded) => assert_eq!(numbers, decoded),
+            Err(HarshError::Decode(
+                DecodeError::InputLength | DecodeError::ValueCount | DecodeError::ValueMagnitude,
+            )) => return TestResult::discard(),
+            Err(e) => panic!("{} failed to decode: {}", encoded, e),
+        }
+        TestResult::passed()
+    }
+}
+
+quickcheck! {
+    fn set_always_decodable(numbers: Vec<u64>) -> TestResult {
+        if numbers.is_empty() {
+            return TestResult::discard();
+        }
+        let harsh = Harsh::default();
+        let encoded = harsh.encode_set(&numbers).expect("Unable to encode set");
+
+        let mut expected = numbers;
+        expected.sort_unstable();
+        expected.dedup();
+        assert_eq!(expected, harsh.decode_set(encoded).expect("Unable to decode set"));
+        TestResult::passed()
+    }
+}
+
+#[cfg(feature = "keyed")]
+quickcheck! {
+    fn permuted_always_decodable(key: Vec<u8>, width: u8, numbers: Vec<u64>) -> TestResult {
+        let width = u32::from(width % 63) + 2;
+        let harsh = match PermutedHarsh::with_width(Harsh::default(), key, width) {
+            Ok(harsh) => harsh,
+            Err(_) => return TestResult::discard(),
+        };
+        let numbers: Vec<_> = numbers.into_iter().map(|n| n >> (64 - width)).collect();
+        if numbers.is_empty() {
+            return TestResult::discard();
+        }
+        let encoded = harsh.encode(&numbers);
+        assert_eq!(numbers, harsh.decode(encoded).expect("Unable to decode value"));
+        TestResult::passed()
+    }
+}
+
+#[cfg(feature = "keyed")]
+quickcheck! {
+    fn signed_always_decodable(secret: Vec<u8>, tag_length: u8, numbers: Vec<u64>, context: Vec<u8>) -> TestResult {
+        let tag_length = usize::from(tag_length % 8) + 1;
+        let harsh = match SignedHarsh::with_tag_length(Harsh::default(), secret, tag_length) {
+            Ok(harsh) => harsh,
+            Err(_) => return TestResult::discard(),
+        };
+        if numbers.is_empty() {
+            return TestResult::discard();
+        }
+        let encoded = harsh.encode_with_context(&numbers, &context).expect("Unable to encode value");
+        assert_eq!(numbers, harsh.decode_with_context(encoded, &context).expect("Unable to decode value"));
+        TestResult::passed()
+    }
+}
@@ -0,0 +1,50 @@
+use harsh::{Harsh, TraceStep};
+
+#[test]
+fn trace_records_decorations() {
+    let harsh = Harsh::builder()
+        .salt("this is my salt")
+        .version(1)
+        .prefix("usr")
+        .check_character(true)
+        .build()
+        .unwrap();
+    let trace = harsh.encode_traced(&[42]).unwrap();
+
+    assert_eq!(harsh.encode(&[42]), trace.hashid);
+    assert!(matches!(
+        trace.steps[trace.steps.len() - 3],
+        TraceStep::Version { .. }
+    ));
+    assert!(matches!(
+        trace.steps[trace.steps.len() - 2],
+        TraceStep::Check { .. }
+    ));
+    assert_eq!(
+        Some(&TraceStep::Prefix {
+            prefix: "usr".to_owned()
+        }),
+        trace.steps.last()
+    );
+}
+
+#[test]
+fn trace_fails_like_encode() {
+    let harsh = Harsh::builder().exact_length(4).build().unwrap();
+    assert_eq!(
+        harsh.try_encode(&[u64::MAX]).unwrap_err(),
+        harsh.encode_traced(&[u64::MAX]).unwrap_err()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn trace_serializes_tagged_steps() {
+    let harsh = Harsh::default();
+    let trace = harsh.encode_traced(&[1]).unwrap();
+    let json = serde_json::to_value(&trace).unwrap();
+
+    assert_eq!("nhash", json["steps"][0]["step"]);
+    assert_eq!(1, json["steps"][0]["value"]);
+    assert_eq!(trace, serde_json::from_value(json).unwrap());
+}